@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fmt::Display;
+use std::path::Path;
 use std::str::FromStr;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
@@ -35,6 +36,30 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Recovers the [`Day`] of a solution file from its path, following the naming scheme of
+    /// the solution files (`AOC_BIN_NAME`). Returns [`None`] if the file name does not match it.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use advent_of_code::Day;
+    /// assert_eq!(Day::from_path(Path::new("./src/bin/07.rs")), Day::new(7));
+    /// assert_eq!(Day::from_path(Path::new("./src/bin/utils.rs")), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file = path.file_name()?.to_str()?;
+        crate::template::paths::parse_bin_file(&crate::template::paths::bin_name_pattern(), file)
+    }
+
+    /// Returns the day following this one, or [`None`] if this is the 25th.
+    pub fn next(self) -> Option<Self> {
+        Self::new(self.0 + 1)
+    }
+
+    /// Returns the day preceding this one, or [`None`] if this is the 1st.
+    pub fn previous(self) -> Option<Self> {
+        Self::new(self.0 - 1)
+    }
 }
 
 impl Display for Day {
@@ -80,6 +105,28 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Day {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Day {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        Self::new(value).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(value.into()),
+                &"a day number between 1 and 25",
+            )
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -88,12 +135,16 @@ pub fn all_days() -> AllDays {
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self {
+            current: 1,
+            last: 25,
+        }
     }
 }
 
@@ -101,7 +152,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
         // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
@@ -110,8 +161,28 @@ impl Iterator for AllDays {
 
         Some(day)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from((self.last + 1).saturating_sub(self.current));
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for AllDays {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current > self.last {
+            return None;
+        }
+        // NOTE: the iterator ends at 25 and we have verified that the value is not below 1.
+        let day = Day(self.last);
+        self.last -= 1;
+
+        Some(day)
+    }
 }
 
+impl ExactSizeIterator for AllDays {}
+
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
@@ -135,8 +206,33 @@ macro_rules! day {
 #[cfg(test)]
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::path::Path;
+
     use super::{all_days, Day};
 
+    #[test]
+    fn display_zero_padded() {
+        // the paths of the inputs, examples and solutions rely on the two digits.
+        assert_eq!(Day(1).to_string(), "01");
+        assert_eq!(Day(9).to_string(), "09");
+        assert_eq!(Day(10).to_string(), "10");
+        assert_eq!(Day(25).to_string(), "25");
+    }
+
+    #[test]
+    fn from_path() {
+        assert_eq!(Day::from_path(Path::new("./src/bin/07.rs")), Some(Day(7)));
+        assert_eq!(Day::from_path(Path::new("src/bin/25.rs")), Some(Day(25)));
+        assert_eq!(Day::from_path(Path::new("01.rs")), Some(Day(1)));
+
+        assert_eq!(Day::from_path(Path::new("./src/bin/7.rs")), None);
+        assert_eq!(Day::from_path(Path::new("./src/bin/00.rs")), None);
+        assert_eq!(Day::from_path(Path::new("./src/bin/26.rs")), None);
+        assert_eq!(Day::from_path(Path::new("./src/bin/07.txt")), None);
+        assert_eq!(Day::from_path(Path::new("./src/bin/utils.rs")), None);
+        assert_eq!(Day::from_path(Path::new("./src/bin")), None);
+    }
+
     #[test]
     fn all_days_iterator() {
         let mut iter = all_days();
@@ -168,6 +264,63 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_reversed() {
+        let days: Vec<Day> = all_days().rev().collect();
+        let expected: Vec<Day> = (1..=25).rev().map(Day).collect();
+        assert_eq!(days, expected);
+    }
+
+    #[test]
+    fn all_days_from_both_ends() {
+        let mut iter = all_days();
+        assert_eq!(iter.len(), 25);
+
+        assert_eq!(iter.next(), Some(Day(1)));
+        assert_eq!(iter.next_back(), Some(Day(25)));
+        assert_eq!(iter.len(), 23);
+
+        let rest: Vec<Day> = iter.by_ref().collect();
+        assert_eq!(rest, (2..=24).map(Day).collect::<Vec<Day>>());
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn next_day() {
+        assert_eq!(Day(1).next(), Some(Day(2)));
+        assert_eq!(Day(12).next(), Some(Day(13)));
+        assert_eq!(Day(24).next(), Some(Day(25)));
+        assert_eq!(Day(25).next(), None);
+    }
+
+    #[test]
+    fn previous_day() {
+        assert_eq!(Day(1).previous(), None);
+        assert_eq!(Day(2).previous(), Some(Day(1)));
+        assert_eq!(Day(13).previous(), Some(Day(12)));
+        assert_eq!(Day(25).previous(), Some(Day(24)));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize() {
+        use serde::de::{value, Deserialize, IntoDeserializer};
+
+        let deserialize = |value: u8| {
+            Day::deserialize(IntoDeserializer::<value::Error>::into_deserializer(value))
+        };
+
+        assert_eq!(deserialize(1).unwrap(), Day(1));
+        assert_eq!(deserialize(25).unwrap(), Day(25));
+        assert!(deserialize(0).is_err());
+        assert_eq!(
+            deserialize(26).unwrap_err().to_string(),
+            "invalid value: integer `26`, expected a day number between 1 and 25"
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -1,5 +1,6 @@
 mod day;
 mod part;
+pub mod prelude;
 pub mod template;
 
 pub use day::*;
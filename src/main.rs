@@ -1,55 +1,178 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, benchmarks, check_examples, download, open, paste, read, scaffold, solve, status, time,
+    verify,
+};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
+    use std::time::Duration;
 
+    use advent_of_code::template::aoc_cli;
+    use advent_of_code::template::benchmark_history::Smoothing;
+    use advent_of_code::template::commands::all::AllOptions;
+    use advent_of_code::template::logging;
+    use advent_of_code::template::readme_benchmarks::TotalOptions;
+    use advent_of_code::template::theme;
+    use advent_of_code::template::RunOptions;
     use advent_of_code::Day;
 
     pub enum AppArguments {
+        Benchmarks {
+            day: Option<Day>,
+            summary: bool,
+        },
+        CheckExamples,
         Download {
             day: Day,
+            retries: u32,
+        },
+        Open {
+            day: Day,
+        },
+        Paste {
+            day: Day,
+            force: bool,
         },
         Read {
             day: Day,
+            stats: bool,
+            compare_input: Option<String>,
         },
         Scaffold {
-            day: Day,
+            day: Option<Day>,
+            format: bool,
+            shared_example: bool,
+            init: Option<u16>,
+            template: Option<String>,
         },
         Solve {
             day: Day,
-            release: bool,
-            time: bool,
+            options: RunOptions,
             submit: Option<u8>,
+            stdin: bool,
+            input: Option<String>,
+            verify_checksum: bool,
+            answers_only: bool,
         },
-        All {
+        Status {
+            json: bool,
+        },
+        Time {
+            day: Day,
+            options: RunOptions,
+        },
+        Verify {
+            day: Day,
             release: bool,
-            time: bool,
+        },
+        All {
+            options: RunOptions,
+            all_options: AllOptions,
+            profile: Option<Day>,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
+        // the color choice applies to every command, it is set before any output.
+        let color = args.opt_value_from_str("--color")?;
+        theme::set_color_choice(color.unwrap_or_else(theme::env_color_choice));
+        logging::set_verbosity(parse_verbosity(&mut args));
+
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+            Some("all") => {
+                let (options, all_options, profile) = parse_all(&mut args, false)?;
+                AppArguments::All {
+                    options,
+                    all_options,
+                    profile,
+                }
+            }
+            Some("benchmarks") => AppArguments::Benchmarks {
+                summary: args.contains("--summary"),
+                day: args.opt_free_from_str()?,
             },
+            Some("check-examples") => AppArguments::CheckExamples,
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                retries: args
+                    .opt_value_from_str("--retries")?
+                    .unwrap_or(aoc_cli::DEFAULT_DOWNLOAD_RETRIES),
+            },
+            Some("open") => AppArguments::Open {
+                day: args.free_from_str()?,
+            },
+            Some("paste") => AppArguments::Paste {
+                force: args.contains("--force"),
+                day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                stats: args.contains("--stats"),
+                compare_input: args.opt_value_from_str("--compare-input")?,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                format: args.contains("--format"),
+                shared_example: args.contains("--shared-example"),
+                init: args.opt_value_from_str("--init")?,
+                template: args.opt_value_from_str("--template")?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let samples = args.opt_value_from_str("--samples")?;
+                let min_time = args
+                    .opt_value_from_str("--min-time")?
+                    .map(Duration::from_millis);
+                let time = args.contains("--time") || samples.is_some() || min_time.is_some();
+                let stdin = args.contains("--stdin");
+                let input = args.opt_value_from_str("--input")?;
+                let answers_only = args.contains("--answers-only");
+                if answers_only && time {
+                    return Err("--answers-only cannot be used with --time".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    options: RunOptions {
+                        release,
+                        time,
+                        samples,
+                        min_time,
+                        example: args.contains("--example"),
+                        part: args.opt_value_from_str("--part")?,
+                        seed: args.opt_value_from_str("--seed")?,
+                        ..Default::default()
+                    },
+                    submit,
+                    stdin,
+                    input,
+                    verify_checksum: args.contains("--verify-checksum"),
+                    answers_only,
+                }
+            }
+            Some("status") => AppArguments::Status {
+                json: args.contains("--json"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("time") => {
+                let (options, all_options, profile) = parse_all(&mut args, true)?;
+                // without a day, every day is benchmarked as with `cargo all --release --time`.
+                match args.opt_free_from_str()? {
+                    Some(day) => AppArguments::Time { day, options },
+                    None => AppArguments::All {
+                        options,
+                        all_options,
+                        profile,
+                    },
+                }
+            }
+            Some("verify") => AppArguments::Verify {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -68,25 +191,203 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses the options of `cargo all`, with `timed` the solutions are always benchmarked in
+    /// release mode, as for `cargo time`.
+    fn parse_all(
+        args: &mut pico_args::Arguments,
+        timed: bool,
+    ) -> Result<(RunOptions, AllOptions, Option<Day>), Box<dyn std::error::Error>> {
+        let release = timed || args.contains("--release");
+        let samples = args.opt_value_from_str("--samples")?;
+        let min_time = args
+            .opt_value_from_str("--min-time")?
+            .map(Duration::from_millis);
+        // sampling options only make sense when benchmarking.
+        let time = timed || args.contains("--time") || samples.is_some() || min_time.is_some();
+        let smoothing = parse_smoothing(args)?;
+        let precision: Option<usize> = args.opt_value_from_str("--precision")?;
+        if precision == Some(0) {
+            return Err("--precision must be at least 1".into());
+        }
+        let fail_fast = args.contains("--fail-fast");
+        // running every day is the default, `--keep-going` only makes it explicit.
+        let keep_going = args.contains("--keep-going");
+        if fail_fast && keep_going {
+            return Err("--fail-fast and --keep-going cannot be used together".into());
+        }
+
+        Ok((
+            RunOptions {
+                release,
+                time,
+                samples,
+                min_time,
+                example: args.contains("--examples"),
+                timeout: args
+                    .opt_value_from_str("--timeout")?
+                    .map(Duration::from_secs),
+                part: args.opt_value_from_str("--part")?,
+                seed: args.opt_value_from_str("--seed")?,
+                ..Default::default()
+            },
+            AllOptions {
+                require_all: args.contains("--require-all"),
+                only: args.opt_value_from_str("--only")?,
+                quiet: args.contains("--quiet"),
+                verbose: logging::is_enabled(logging::VERBOSE),
+                no_readme: args.contains("--no-readme"),
+                sort_by_time: args.contains("--sort-by-time"),
+                no_links: args.contains("--no-links"),
+                max_answer_width: args.opt_value_from_str("--max-answer-width")?,
+                prebuild: args.contains("--prebuild"),
+                fail_fast,
+                clear_readme: args.contains("--clear-readme"),
+                store: args.contains("--store"),
+                total: TotalOptions {
+                    hidden: args.contains("--no-total"),
+                    label: args.opt_value_from_str("--total-label")?,
+                    millis: args.contains("--total-millis"),
+                },
+                smoothing,
+                existing_only: args.contains("--existing-only"),
+                precision,
+                all_years: args.contains("--all-years"),
+                summary_line: args.contains("--summary-line"),
+                prune: args.contains("--prune"),
+                header: args.opt_value_from_str("--header")?,
+                dry_run: args.contains("--dry-run"),
+                show_samples: args.contains("--show-samples"),
+                group_by_week: args.contains("--group-by-week"),
+            },
+            args.opt_value_from_str("--profile")?,
+        ))
+    }
+
+    /// Parses the verbosity shared by every command, each `-v` or `--verbose` raising it by one,
+    /// e.g. `-vv` for [`logging::VERY_VERBOSE`].
+    fn parse_verbosity(args: &mut pico_args::Arguments) -> u8 {
+        let mut verbosity = 0_u8;
+        while args.contains(["-v", "--verbose"]) {
+            verbosity = verbosity.saturating_add(1);
+        }
+        while args.contains("-vv") {
+            verbosity = verbosity.saturating_add(2);
+        }
+        verbosity
+    }
+
+    /// Parses the smoothing of the README timings. Setting the window or the weight of the moving
+    /// average enables it as well as `--smooth`.
+    fn parse_smoothing(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Smoothing>, Box<dyn std::error::Error>> {
+        let smooth = args.contains("--smooth");
+        let window: Option<usize> = args.opt_value_from_str("--ema-window")?;
+        let alpha: Option<f64> = args.opt_value_from_str("--ema-alpha")?;
+
+        if !smooth && window.is_none() && alpha.is_none() {
+            return Ok(None);
+        }
+        if window == Some(0) {
+            return Err("--ema-window must be at least 1".into());
+        }
+        if alpha.is_some_and(|alpha| !(alpha > 0_f64 && alpha <= 1_f64)) {
+            return Err("--ema-alpha must be greater than 0 and at most 1".into());
+        }
+
+        let default = Smoothing::default();
+        Ok(Some(Smoothing {
+            window: window.unwrap_or(default.window),
+            alpha: alpha.unwrap_or(default.alpha),
+        }))
+    }
+
+    #[cfg(test)]
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use std::ffi::OsString;
+
+        use super::parse_all;
+
+        fn arguments(args: &[&str]) -> pico_args::Arguments {
+            pico_args::Arguments::from_vec(args.iter().map(OsString::from).collect())
+        }
+
+        #[test]
+        fn consumes_keep_going() {
+            let mut args = arguments(&["--keep-going", "--only", "25"]);
+            let (_, options, _) = parse_all(&mut args, false).unwrap();
+            assert!(!options.fail_fast);
+            assert!(args.finish().is_empty());
+        }
+
+        #[test]
+        fn rejects_fail_fast_with_keep_going() {
+            let mut args = arguments(&["--fail-fast", "--keep-going"]);
+            assert!(parse_all(&mut args, false).is_err());
+        }
+    }
 }
 
 fn main() {
+    #[cfg(feature = "log")]
+    advent_of_code::template::logging::init_cli_logger();
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                options,
+                all_options,
+                profile,
+            } => match profile {
+                Some(day) => all::profile(day, &options),
+                None => all::handle(options, all_options),
+            },
+            AppArguments::Benchmarks { day, summary } => benchmarks::handle(day, summary),
+            AppArguments::CheckExamples => check_examples::handle(),
+            AppArguments::Download { day, retries } => download::handle(day, retries),
+            AppArguments::Open { day } => open::handle(day),
+            AppArguments::Paste { day, force } => paste::handle(day, force),
+            AppArguments::Read {
+                day,
+                stats,
+                compare_input,
+            } => read::handle(day, stats, compare_input),
+            AppArguments::Scaffold {
+                day,
+                format,
+                shared_example,
+                init,
+                template,
+            } => match init {
+                Some(year) => scaffold::init(year),
+                None => scaffold::handle(day, format, shared_example, template),
+            },
             AppArguments::Solve {
                 day,
-                release,
-                time,
+                options,
+                submit,
+                stdin,
+                input,
+                verify_checksum,
+                answers_only,
+            } => solve::handle(
+                day,
+                options,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                stdin,
+                input,
+                verify_checksum,
+                answers_only,
+            ),
+            AppArguments::Status { json } => status::handle(json),
+            AppArguments::Time { day, options } => time::handle(day, options),
+            AppArguments::Verify { day, release } => verify::handle(day, release),
         },
     };
 }
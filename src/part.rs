@@ -5,7 +5,7 @@ use std::str::FromStr;
 /// A valid part number of advent (i.e. either 1 or 2).
 ///
 /// # Display
-/// This value displays as a two digit number.
+/// This value displays as a single digit number, parsing accepts it with or without a leading zero.
 ///
 /// ```
 /// # use advent_of_code::Part;
@@ -16,6 +16,15 @@ use std::str::FromStr;
 pub struct Part(u8);
 
 impl Part {
+    /// Both parts of a puzzle, in order.
+    ///
+    /// ```
+    /// # use advent_of_code::Part;
+    /// let parts: Vec<u8> = Part::ALL.into_iter().map(Part::into_inner).collect();
+    /// assert_eq!(parts, [1, 2]);
+    /// ```
+    pub const ALL: [Part; 2] = [Self(1), Self(2)];
+
     /// Creates a [`Part`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(part: u8) -> Option<Self> {
@@ -35,11 +44,22 @@ impl Part {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the opposite [`Part`] (i.e. the second part for the first one and vice versa).
+    ///
+    /// ```
+    /// # use advent_of_code::Part;
+    /// assert_eq!(Part::new(1).unwrap().other(), Part::new(2).unwrap());
+    /// assert_eq!(Part::new(2).unwrap().other(), Part::new(1).unwrap());
+    /// ```
+    pub const fn other(self) -> Self {
+        Self(3 - self.0)
+    }
 }
 
 impl Display for Part {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:01}", self.0)
+        write!(f, "{}", self.0)
     }
 }
 
@@ -55,6 +75,30 @@ impl PartialOrd<u8> for Part {
     }
 }
 
+impl PartialEq<Part> for u8 {
+    fn eq(&self, other: &Part) -> bool {
+        self.eq(&other.0)
+    }
+}
+
+impl PartialOrd<Part> for u8 {
+    fn partial_cmp(&self, other: &Part) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        part.0
+    }
+}
+
+impl From<Part> for u32 {
+    fn from(part: Part) -> Self {
+        part.0.into()
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl FromStr for Part {
@@ -66,7 +110,7 @@ impl FromStr for Part {
     }
 }
 
-/// An error which can be returned when parsing a [`Day`].
+/// An error which can be returned when parsing a [`Part`].
 #[derive(Debug)]
 pub struct PartFromStrError;
 
@@ -80,6 +124,28 @@ impl Display for PartFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Part {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Part {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        Self::new(value).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(value.into()),
+                &"a part, either 1 or 2",
+            )
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Part`] value in a const context.
 #[macro_export]
 macro_rules! part {
@@ -99,3 +165,85 @@ macro_rules! part {
         const PART_TWO: advent_of_code::Part = advent_of_code::part!(2);
     };
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Part;
+
+    #[test]
+    fn from_str_display_round_trip() {
+        for part in [Part(1), Part(2)] {
+            assert_eq!(part.to_string().parse::<Part>().unwrap(), part);
+        }
+
+        assert_eq!("1".parse::<Part>().unwrap().to_string(), "1");
+        assert_eq!("2".parse::<Part>().unwrap().to_string(), "2");
+    }
+
+    #[test]
+    fn from_str_leading_zero() {
+        assert_eq!("01".parse::<Part>().unwrap(), Part(1));
+        assert_eq!("02".parse::<Part>().unwrap(), Part(2));
+    }
+
+    #[test]
+    fn all_parts() {
+        assert_eq!(Part::ALL, [Part(1), Part(2)]);
+    }
+
+    #[test]
+    fn other_part() {
+        assert_eq!(Part(1).other(), Part(2));
+        assert_eq!(Part(2).other(), Part(1));
+        assert_eq!(Part(1).other().other(), Part(1));
+    }
+
+    #[test]
+    fn compares_with_u8() {
+        assert_eq!(Part(1), 1);
+        assert_eq!(1, Part(1));
+        assert_ne!(Part(2), 1);
+        assert_ne!(1, Part(2));
+        assert!(Part(1) < 2);
+        assert!(2 > Part(1));
+    }
+
+    #[test]
+    fn converts_to_integers() {
+        assert_eq!(u8::from(Part(1)), 1);
+        assert_eq!(u32::from(Part(2)), 2);
+        let sum: u32 = Part::ALL.into_iter().map(u32::from).sum();
+        assert_eq!(sum, 3);
+    }
+
+    #[test]
+    fn from_str_invalid() {
+        assert!("0".parse::<Part>().is_err());
+        assert!("3".parse::<Part>().is_err());
+        assert!("-1".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize() {
+        use serde::de::{value, Deserialize, IntoDeserializer};
+
+        let deserialize = |value: u8| {
+            Part::deserialize(IntoDeserializer::<value::Error>::into_deserializer(value))
+        };
+
+        assert_eq!(deserialize(1).unwrap(), Part(1));
+        assert_eq!(deserialize(2).unwrap(), Part(2));
+        assert_eq!(
+            deserialize(0).unwrap_err().to_string(),
+            "invalid value: integer `0`, expected a part, either 1 or 2"
+        );
+        assert!(deserialize(3).is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,5 @@
+//! The items commonly used by solutions, imported at once with
+//! `use advent_of_code::prelude::*;`.
+
+pub use crate::template::{read_example, read_example_answer, read_input, seed};
+pub use crate::{day, part, Day, Part};
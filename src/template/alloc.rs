@@ -0,0 +1,184 @@
+//! Opt-in global allocator that counts the bytes allocated by the solutions, so that the runner
+//! can report the peak heap usage of each part.
+//!
+//! The allocator is not installed by the [`crate::solution`] macro, so that solutions remain free
+//! to use another global allocator. Set it in the solution of a day to report the heap usage:
+//!
+//! ```
+//! advent_of_code::solution!(1);
+//!
+//! #[global_allocator]
+//! static ALLOC: advent_of_code::template::alloc::CountingAllocator =
+//!     advent_of_code::template::alloc::CountingAllocator;
+//! ```
+//!
+//! The bytes are counted per thread, so that concurrent measures (e.g. tests running in parallel)
+//! do not mix up their allocations. Allocations made by other threads, such as the workers of a
+//! thread pool, are not counted. Outside of a measure, the allocator only checks a thread-local
+//! flag on top of the system allocator. The runner still measures the heap in a dedicated
+//! execution, separate from the timed samples.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// The allocation counters of a thread.
+struct Counters {
+    measuring: Cell<bool>,
+    current: Cell<usize>,
+    peak: Cell<usize>,
+}
+
+thread_local! {
+    // const initialized without destructor, so that accessing it never allocates.
+    static COUNTERS: Counters = const {
+        Counters {
+            measuring: Cell::new(false),
+            current: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// Wraps the system allocator and counts the bytes allocated by the current thread while a
+/// measure is in progress, see [`reset`] and [`read`].
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+fn grow(size: usize) {
+    // the counters are gone while the thread shuts down, its allocations are not measured anyway.
+    let _ = COUNTERS.try_with(|counters| {
+        if counters.measuring.get() {
+            let current = counters.current.get() + size;
+            counters.current.set(current);
+            counters.peak.set(counters.peak.get().max(current));
+        }
+    });
+}
+
+fn shrink(size: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        if counters.measuring.get() {
+            // memory allocated before the measure started may be freed during it.
+            counters
+                .current
+                .set(counters.current.get().saturating_sub(size));
+        }
+    });
+}
+
+/// Resets the counters of the current thread and starts measuring its allocations.
+pub fn reset() {
+    COUNTERS.with(|counters| {
+        counters.current.set(0);
+        counters.peak.set(0);
+        counters.measuring.set(true);
+    });
+}
+
+/// Stops measuring the allocations of the current thread and returns the peak number of bytes it
+/// held on the heap since the last [`reset`].
+pub fn read() -> u64 {
+    COUNTERS.with(|counters| {
+        counters.measuring.set(false);
+        counters.peak.get() as u64
+    })
+}
+
+/// Returns whether [`CountingAllocator`] is the global allocator, by measuring a probe allocation.
+#[must_use]
+pub fn is_installed() -> bool {
+    reset();
+    drop(std::hint::black_box(Box::new(0_u8)));
+    read() > 0
+}
+
+/// Runs `func` and returns its result with the peak number of bytes it held on the heap.
+/// Only allocations of the current thread made through [`CountingAllocator`] are counted.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, u64) {
+    reset();
+    let result = func();
+    (result, read())
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::thread;
+
+    use super::{is_installed, measure, read, reset, CountingAllocator};
+
+    #[global_allocator]
+    static ALLOC: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn measures_peak_allocation() {
+        let (len, bytes) = measure(|| {
+            let first = vec![0_u8; 4096];
+            drop(first);
+            let second = vec![0_u8; 1024];
+            second.len()
+        });
+
+        assert_eq!(len, 1024);
+        assert_eq!(bytes, 4096);
+    }
+
+    #[test]
+    fn measures_overlapping_allocations() {
+        reset();
+        let first = vec![0_u8; 1000];
+        let second = vec![0_u8; 2000];
+        drop(first);
+        drop(second);
+        assert_eq!(read(), 3000);
+    }
+
+    #[test]
+    fn ignores_other_threads() {
+        let (_, bytes) = measure(|| {
+            thread::spawn(|| vec![0_u8; 1 << 20].len()).join().unwrap();
+            vec![0_u8; 100].len()
+        });
+
+        // the spawned thread itself allocates a bit on this thread, but not its megabyte.
+        assert!((100..1 << 20).contains(&bytes));
+    }
+
+    #[test]
+    fn detects_installation() {
+        assert!(is_installed());
+    }
+}
@@ -1,9 +1,15 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
+    path::PathBuf,
     process::{Command, Output, Stdio},
+    thread,
+    time::Duration,
 };
 
+use crate::template::logging::{self, info, warning};
+use crate::template::paths;
 use crate::Day;
 
 #[derive(Debug)]
@@ -11,6 +17,7 @@ pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    InvalidSession(Output),
     IoError,
 }
 
@@ -22,6 +29,9 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::InvalidSession(_) => {
+                write!(f, "your session cookie is invalid or has expired.")
+            }
             AocCommandError::IoError => write!(f, "could not write output files to file system."),
         }
     }
@@ -36,7 +46,7 @@ pub fn check() -> Result<(), AocCommandError> {
 }
 
 pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+    let puzzle_path = paths::puzzle_path(day);
 
     let args = build_args(
         "read",
@@ -51,9 +61,42 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// The default number of times a failed download is retried.
+pub const DEFAULT_DOWNLOAD_RETRIES: u32 = 2;
+
+/// The delay before the first retry of a download, doubled after each failed attempt.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Downloads the input and puzzle of a day, retrying with an exponential backoff when aoc-cli
+/// fails for a reason that might be temporary (e.g. a timeout or a server error).
+/// An invalid session cookie or another client error (e.g. a puzzle that is not unlocked yet) is
+/// never retried.
+pub fn download(day: Day, retries: u32) -> Result<Output, AocCommandError> {
+    let mut delay = INITIAL_RETRY_DELAY;
+    let mut retry = 0;
+
+    loop {
+        match download_once(day) {
+            Err(AocCommandError::BadExitStatus(output))
+                if retry < retries
+                    && is_transient_error(&String::from_utf8_lossy(&output.stderr)) =>
+            {
+                retry += 1;
+                warning!(
+                    "Download failed, retrying in {}s ({retry}/{retries})...",
+                    delay.as_secs()
+                );
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn download_once(day: Day) -> Result<Output, AocCommandError> {
+    let input_path = paths::input_path(day);
+    let puzzle_path = paths::puzzle_path(day);
 
     let args = build_args(
         "download",
@@ -69,8 +112,8 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
 
     let output = call_aoc_cli(&args)?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    info!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    info!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
 
@@ -82,21 +125,40 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
-
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
     }
 }
 
+/// The environment variable holding the session cookie read by aoc-cli.
+const SESSION_VAR: &str = "ADVENT_OF_CODE_SESSION";
+
+/// The files holding the session cookie read by aoc-cli: `.adventofcode.session` in the home
+/// directory and `adventofcode.session` in the configuration directory.
+fn session_files() -> Vec<PathBuf> {
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    home.map(|home| home.join(".adventofcode.session"))
+        .into_iter()
+        .chain(config.map(|config| config.join("adventofcode.session")))
+        .collect()
+}
+
+/// Returns `true` if a session cookie is configured for aoc-cli, either in its environment variable
+/// or in one of its session files. The cookie itself is not checked and may have expired.
+#[must_use]
+pub fn has_session() -> bool {
+    env::var(SESSION_VAR).is_ok_and(|session| !session.trim().is_empty())
+        || session_files().iter().any(|path| path.is_file())
+}
+
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
@@ -110,18 +172,90 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
+/// Returns `true` if aoc-cli failed because of a missing, invalid or expired session cookie.
+/// The "Loaded session cookie from ..." line that aoc-cli logs on every call is not an error.
+fn is_session_error(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    http_status(&stderr) == Some(401)
+        || stderr.contains("unauthorized")
+        || stderr.contains("invalid session cookie")
+        || stderr.contains("session cookie file not found")
+}
+
+/// Returns `true` if aoc-cli failed for a reason that might be temporary, i.e. a timeout or a
+/// server error. Client errors, e.g. a puzzle that is not unlocked yet, are never retried.
+fn is_transient_error(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    stderr.contains("timed out")
+        || stderr.contains("timeout")
+        || http_status(&stderr).is_some_and(|status| (500..600).contains(&status))
+}
+
+/// Returns the HTTP status reported by aoc-cli, e.g. `404` for
+/// `HTTP status client error (404 Not Found) for url (...)`.
+fn http_status(stderr: &str) -> Option<u16> {
+    let (_, status) = stderr.split_once("http status")?;
+    let (_, status) = status.split_once('(')?;
+    status.get(..3)?.parse().ok()
+}
+
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
+    logging::trace_command("aoc", args);
     let output = Command::new("aoc")
         .args(args)
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stderr(Stdio::piped())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    // stderr is captured to detect session errors, forward it once the command exited.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    eprint!("{stderr}");
+
     if output.status.success() {
         Ok(output)
+    } else if is_session_error(&stderr) {
+        Err(AocCommandError::InvalidSession(output))
     } else {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_session_error, is_transient_error};
+
+    #[test]
+    fn detects_session_errors() {
+        assert!(is_session_error("Error: Invalid session cookie"));
+        assert!(is_session_error(
+            "HTTP status client error (401 Unauthorized)"
+        ));
+        assert!(!is_session_error(
+            "HTTP status server error (502 Bad Gateway)"
+        ));
+        assert!(!is_session_error("operation timed out"));
+    }
+
+    #[test]
+    fn detects_transient_errors() {
+        assert!(is_transient_error(
+            "HTTP status server error (502 Bad Gateway)"
+        ));
+        assert!(is_transient_error("operation timed out"));
+        assert!(!is_transient_error(
+            "HTTP status client error (401 Unauthorized)"
+        ));
+    }
+
+    #[test]
+    fn ignores_loaded_session_cookie() {
+        let stderr =
+            "[INFO  aoc] Loaded session cookie from \"/home/user/.adventofcode.session\".\n\
+                      Error: HTTP status client error (404 Not Found) for url \
+                      (https://adventofcode.com/2024/day/26/input)";
+        assert!(!is_session_error(stderr));
+        assert!(!is_transient_error(stderr));
+    }
+}
@@ -0,0 +1,423 @@
+/// Module that records the benchmarks of every timed run in a history file, one JSON record per
+/// line, so that timings can be compared over time.
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::template::commands::all::parse_duration;
+use crate::template::json::{self, Value};
+use crate::template::paths;
+use crate::template::readme_benchmarks::{Benchmark, Bytes};
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    /// A line of the history could not be parsed, lines are numbered from 1.
+    Parser {
+        line: usize,
+        message: String,
+    },
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser { line, message } => {
+                write!(f, "could not parse line {line} of the history: {message}")
+            }
+            Error::IO(e) => write!(f, "could not read or write the history: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(e) => Some(e),
+            Error::Parser { .. } => None,
+        }
+    }
+}
+
+/// The benchmark of a day recorded in the history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// When the benchmark was recorded, in seconds since the unix epoch.
+    pub timestamp: u64,
+    pub day: Day,
+    /// The timing of part one as printed by the solution, e.g. `1.2ms`.
+    pub part_1: Option<String>,
+    /// The timing of part two as printed by the solution, e.g. `1.2ms`.
+    pub part_2: Option<String>,
+    pub total_nanos: f64,
+}
+
+impl Record {
+    /// Creates the record of a benchmark.
+    pub fn new(bench: &Benchmark, timestamp: u64) -> Self {
+        Self {
+            timestamp,
+            day: bench.day,
+            part_1: bench.part_1.as_ref().map(|(timing, _)| timing.clone()),
+            part_2: bench.part_2.as_ref().map(|(timing, _)| timing.clone()),
+            total_nanos: bench.total_nanos,
+        }
+    }
+
+    /// Serializes the record to a single line JSON object.
+    pub fn to_json(&self) -> String {
+        let part = |part: &Option<String>| part.as_deref().map_or("null".into(), json::quote);
+        format!(
+            r#"{{"timestamp":{},"day":{},"part_1":{},"part_2":{},"total_nanos":{}}}"#,
+            self.timestamp,
+            self.day.into_inner(),
+            part(&self.part_1),
+            part(&self.part_2),
+            self.total_nanos
+        )
+    }
+
+    /// Parses a record serialized with [`Record::to_json`].
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let object = json::parse_object(s)?;
+        let number = |key: &str| match object.get(key) {
+            Some(Value::Number(n)) => Ok(*n),
+            _ => Err(format!("expected a number for `{key}`")),
+        };
+        let part = |key: &str| match object.get(key) {
+            Some(Value::String(timing)) => Ok(Some(timing.clone())),
+            Some(Value::Null) | None => Ok(None),
+            _ => Err(format!("expected a string for `{key}`")),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let day = Day::new(number("day")? as u8).ok_or("expected a day between 1 and 25")?;
+
+        Ok(Self {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            timestamp: number("timestamp")? as u64,
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+            total_nanos: number("total_nanos")?,
+        })
+    }
+}
+
+/// The best, worst and median total time of a day over its recorded runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub day: Day,
+    pub runs: usize,
+    pub best_nanos: f64,
+    pub worst_nanos: f64,
+    pub median_nanos: f64,
+}
+
+/// Summarizes the records of each day, ordered by day.
+pub fn summarize(records: &[Record]) -> Vec<Summary> {
+    let mut totals: BTreeMap<Day, Vec<f64>> = BTreeMap::new();
+    for record in records {
+        totals
+            .entry(record.day)
+            .or_default()
+            .push(record.total_nanos);
+    }
+
+    totals
+        .into_iter()
+        .map(|(day, mut totals)| {
+            totals.sort_by(f64::total_cmp);
+            let middle = totals.len() / 2;
+            let median_nanos = if totals.len() % 2 == 0 {
+                (totals[middle - 1] + totals[middle]) / 2_f64
+            } else {
+                totals[middle]
+            };
+
+            Summary {
+                day,
+                runs: totals.len(),
+                best_nanos: totals[0],
+                worst_nanos: totals[totals.len() - 1],
+                median_nanos,
+            }
+        })
+        .collect()
+}
+
+/// Settings of the exponential moving average used to smooth the timings of the README.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Smoothing {
+    /// The number of most recent runs of a day included in the average.
+    pub window: usize,
+    /// The weight of the most recent run, between 0 (excluded) and 1. Higher values follow the
+    /// latest runs more closely.
+    pub alpha: f64,
+}
+
+impl Default for Smoothing {
+    fn default() -> Self {
+        Self {
+            window: 5,
+            alpha: 0.5,
+        }
+    }
+}
+
+/// Computes the exponential moving average of the last `window` values, ordered from the oldest
+/// to the most recent. The oldest value seeds the average, then each value `v` updates it to
+/// `alpha * v + (1 - alpha) * average`. Returns [`None`] for an empty slice.
+pub fn ema(values: &[f64], smoothing: Smoothing) -> Option<f64> {
+    let start = values.len().saturating_sub(smoothing.window.max(1));
+    let (first, rest) = values[start..].split_first()?;
+
+    Some(rest.iter().fold(*first, |average, value| {
+        smoothing.alpha * value + (1_f64 - smoothing.alpha) * average
+    }))
+}
+
+/// Replaces the timings of each benchmark with their exponential moving average over the runs of
+/// its day recorded in `history`, the benchmark itself being the most recent run. Parts that were
+/// not timed are left untouched.
+pub fn smooth(benchmarks: &mut [Benchmark], history: &[Record], smoothing: Smoothing) {
+    for bench in benchmarks.iter_mut().filter(|bench| !bench.timed_out) {
+        let records: Vec<&Record> = history
+            .iter()
+            .filter(|record| record.day == bench.day)
+            .collect();
+
+        let mut totals: Vec<f64> = records.iter().map(|record| record.total_nanos).collect();
+        totals.push(bench.total_nanos);
+        bench.total_nanos = ema(&totals, smoothing).unwrap_or(bench.total_nanos);
+
+        let recorded = |part: fn(&Record) -> &Option<String>| -> Vec<f64> {
+            records
+                .iter()
+                .filter_map(|record| part(record).as_deref().and_then(parse_duration))
+                .collect()
+        };
+        smooth_part(
+            &mut bench.part_1,
+            recorded(|record| &record.part_1),
+            smoothing,
+        );
+        smooth_part(
+            &mut bench.part_2,
+            recorded(|record| &record.part_2),
+            smoothing,
+        );
+    }
+}
+
+/// Replaces the timing of a part with its average over the `recorded` timings, in nanoseconds,
+/// and its own timing.
+fn smooth_part(part: &mut Option<(String, Bytes)>, mut recorded: Vec<f64>, smoothing: Smoothing) {
+    let Some((timing, _)) = part else {
+        return;
+    };
+    let Some(nanos) = parse_duration(timing) else {
+        return;
+    };
+
+    recorded.push(nanos);
+    if let Some(average) = ema(&recorded, smoothing) {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = Duration::from_nanos(average as u64);
+        *timing = format!("{duration:.1?}");
+    }
+}
+
+/// Formats a timestamp as a UTC date and time, e.g. `2023-12-01 05:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    // days to civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let seconds_of_day = timestamp % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60
+    )
+}
+
+/// Parses the lines of a history file, skipping empty lines.
+fn parse_history(s: &str) -> Result<Vec<Record>, Error> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            Record::parse(line).map_err(|message| Error::Parser {
+                line: index + 1,
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Reads every record of the history file.
+pub fn read() -> Result<Vec<Record>, Error> {
+    parse_history(&fs::read_to_string(paths::history_path())?)
+}
+
+/// Appends the benchmarks of a run to the history file, creating it if needed.
+/// Benchmarks of timed out days are not recorded.
+pub fn append(benchmarks: &[Benchmark]) -> Result<(), Error> {
+    let path = paths::history_path();
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let lines: String = benchmarks
+        .iter()
+        .filter(|bench| !bench.timed_out)
+        .map(|bench| format!("{}\n", Record::new(bench, timestamp).to_json()))
+        .collect();
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        ema, format_timestamp, parse_history, smooth, summarize, Error, Record, Smoothing,
+    };
+    use crate::day;
+    use crate::template::readme_benchmarks::{Benchmark, Bytes};
+
+    fn get_record(day: u8, total_nanos: f64) -> Record {
+        Record {
+            timestamp: 1_701_406_800,
+            day: crate::Day::new(day).unwrap(),
+            part_1: Some("1.2\u{b5}s".into()),
+            part_2: None,
+            total_nanos,
+        }
+    }
+
+    #[test]
+    fn records_round_trip() {
+        let record = get_record(1, 1200.5);
+        let json = record.to_json();
+        assert_eq!(
+            json,
+            r#"{"timestamp":1701406800,"day":1,"part_1":"1.2µs","part_2":null,"total_nanos":1200.5}"#
+        );
+        assert_eq!(Record::parse(&json).unwrap(), record);
+    }
+
+    #[test]
+    fn parses_history_lines() {
+        let history = format!(
+            "{}\n\n{}\n",
+            get_record(1, 10.0).to_json(),
+            get_record(2, 20.0).to_json()
+        );
+        let records = parse_history(&history).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].day, day!(2));
+
+        let err = parse_history(&format!("{history}{{\"day\":26}}")).unwrap_err();
+        assert!(matches!(err, Error::Parser { line: 4, .. }));
+    }
+
+    #[test]
+    fn summarizes_days() {
+        let records = [
+            get_record(2, 40.0),
+            get_record(1, 30.0),
+            get_record(1, 10.0),
+            get_record(1, 20.0),
+            get_record(2, 10.0),
+        ];
+        let summaries = summarize(&records);
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].day, day!(1));
+        assert_eq!(summaries[0].runs, 3);
+        assert_eq!(summaries[0].best_nanos, 10.0);
+        assert_eq!(summaries[0].worst_nanos, 30.0);
+        assert_eq!(summaries[0].median_nanos, 20.0);
+        assert_eq!(summaries[1].median_nanos, 25.0);
+    }
+
+    #[test]
+    fn computes_ema() {
+        let smoothing = Smoothing {
+            window: 3,
+            alpha: 0.5,
+        };
+        assert_eq!(ema(&[], smoothing), None);
+        assert_eq!(ema(&[8.0], smoothing), Some(8.0));
+        // only 4, 8 and 2 are in the window: (4 + 8) / 2 = 6, then (6 + 2) / 2 = 4.
+        assert_eq!(ema(&[100.0, 4.0, 8.0, 2.0], smoothing), Some(4.0));
+
+        let smoothing = Smoothing {
+            window: 10,
+            alpha: 0.25,
+        };
+        // 10, then 0.25 * 20 + 0.75 * 10 = 12.5, then 0.25 * 30 + 0.75 * 12.5 = 16.875.
+        assert_eq!(ema(&[10.0, 20.0, 30.0], smoothing), Some(16.875));
+    }
+
+    #[test]
+    fn smooths_benchmarks() {
+        let history = [
+            get_record(1, 1000.0),
+            get_record(2, 50.0),
+            get_record(1, 3000.0),
+        ];
+        let mut benchmarks = [Benchmark {
+            day: day!(1),
+            part_1: Some(("3.0\u{b5}s".into(), Bytes(10))),
+            part_2: Some(("".into(), Bytes(0))),
+            total_nanos: 3000.0,
+            timed_out: false,
+            samples: [None; 2],
+        }];
+        smooth(&mut benchmarks, &history, Smoothing::default());
+
+        // 1000, then (1000 + 3000) / 2 = 2000, then (2000 + 3000) / 2 = 2500.
+        assert_eq!(benchmarks[0].total_nanos, 2500.0);
+        // the part is 1.2µs in both records: 1200, then 1200, then (1200 + 3000) / 2 = 2100.
+        assert_eq!(benchmarks[0].part_1, Some(("2.1\u{b5}s".into(), Bytes(10))));
+        assert_eq!(benchmarks[0].part_2, Some(("".into(), Bytes(0))));
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_701_406_800), "2023-12-01 05:00");
+        assert_eq!(format_timestamp(1_709_210_096), "2024-02-29 12:34");
+    }
+}
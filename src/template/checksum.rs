@@ -0,0 +1,173 @@
+//! SHA-256 checksums of the downloaded inputs, detecting inputs that were corrupted or edited by
+//! accident.
+//!
+//! `cargo download` records the checksum of an input next to it, e.g. `data/inputs/01.sha256`,
+//! and solutions run with `--verify-checksum` warn when their input no longer matches it. Hashing
+//! requires the `checksum` feature, without it nothing is recorded or verified.
+#[cfg(feature = "checksum")]
+use std::path::Path;
+#[cfg(feature = "checksum")]
+use std::{fs, io};
+
+use crate::template::logging::warning;
+#[cfg(feature = "checksum")]
+use crate::template::paths;
+use crate::Day;
+
+/// The result of the verification of an input against its recorded checksum.
+#[cfg(feature = "checksum")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// The input matches its checksum.
+    Match,
+    /// The input changed since its checksum was recorded.
+    Mismatch { expected: String, actual: String },
+    /// No checksum was recorded for the input, e.g. it was pasted rather than downloaded.
+    Unrecorded,
+}
+
+/// Returns the lowercase hexadecimal SHA-256 digest of a content.
+#[cfg(feature = "checksum")]
+#[must_use]
+pub fn sha256(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Records the checksum of the input of a day, see [`paths::checksum_path`].
+#[cfg(feature = "checksum")]
+pub fn record(day: Day) -> io::Result<()> {
+    record_at(
+        Path::new(&paths::input_path(day)),
+        Path::new(&paths::checksum_path(day)),
+    )
+}
+
+/// Verifies the input of a day against its recorded checksum.
+#[cfg(feature = "checksum")]
+pub fn verify(day: Day, input: &str) -> io::Result<Verification> {
+    verify_at(Path::new(&paths::checksum_path(day)), input)
+}
+
+#[cfg(feature = "checksum")]
+fn record_at(input_path: &Path, checksum_path: &Path) -> io::Result<()> {
+    let input = fs::read_to_string(input_path)?;
+    fs::write(checksum_path, format!("{}\n", sha256(&input)))
+}
+
+#[cfg(feature = "checksum")]
+fn verify_at(checksum_path: &Path, input: &str) -> io::Result<Verification> {
+    let recorded = match fs::read_to_string(checksum_path) {
+        Ok(recorded) => recorded,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Verification::Unrecorded),
+        Err(e) => return Err(e),
+    };
+
+    // only the digest is kept, so that files written by `sha256sum` are read as well.
+    let expected = recorded.split_whitespace().next().unwrap_or_default();
+    let actual = sha256(input);
+    if expected.eq_ignore_ascii_case(&actual) {
+        Ok(Verification::Match)
+    } else {
+        Ok(Verification::Mismatch {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+#[cfg(feature = "checksum")]
+fn mismatch_warning(day: Day, expected: &str, actual: &str) -> String {
+    format!(
+        "The input of day {day} does not match its checksum (expected {expected}, got {actual}), \
+         it may have been edited or corrupted. Download it again with `cargo download {day}`."
+    )
+}
+
+/// Verifies the input of a day against its recorded checksum, printing a warning if it does not
+/// match or cannot be verified.
+#[cfg(feature = "checksum")]
+pub fn warn_on_mismatch(day: Day, input: &str) {
+    match verify(day, input) {
+        Ok(Verification::Match) => {}
+        Ok(Verification::Mismatch { expected, actual }) => {
+            warning!("{}", mismatch_warning(day, &expected, &actual));
+        }
+        Ok(Verification::Unrecorded) => {
+            warning!("No checksum recorded for the input of day {day}, it cannot be verified.");
+        }
+        Err(e) => warning!("Failed to read the checksum of the input of day {day}: {e}"),
+    }
+}
+
+#[cfg(not(feature = "checksum"))]
+pub fn warn_on_mismatch(day: Day, _input: &str) {
+    warning!("Verifying the input of day {day} requires the `checksum` feature.");
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+#[cfg(feature = "checksum")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{mismatch_warning, record_at, sha256, verify_at, Verification};
+    use crate::day;
+
+    #[test]
+    fn hashes_content() {
+        assert_eq!(
+            sha256("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn detects_tampered_input() {
+        let dir = env::temp_dir().join(format!("aoc-checksum-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (input_path, checksum_path) = (dir.join("01.txt"), dir.join("01.sha256"));
+
+        assert_eq!(
+            verify_at(&checksum_path, "1 2 3\n").unwrap(),
+            Verification::Unrecorded
+        );
+
+        fs::write(&input_path, "1 2 3\n").unwrap();
+        record_at(&input_path, &checksum_path).unwrap();
+        let input = fs::read_to_string(&input_path).unwrap();
+        assert_eq!(
+            verify_at(&checksum_path, &input).unwrap(),
+            Verification::Match
+        );
+
+        fs::write(&input_path, "1 2 4\n").unwrap();
+        let tampered = fs::read_to_string(&input_path).unwrap();
+        let verification = verify_at(&checksum_path, &tampered).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let Verification::Mismatch { expected, actual } = verification else {
+            panic!("expected a mismatch, got {verification:?}");
+        };
+        assert_eq!(expected, sha256("1 2 3\n"));
+        assert_eq!(actual, sha256("1 2 4\n"));
+        assert!(mismatch_warning(day!(1), &expected, &actual)
+            .starts_with("The input of day 01 does not match its checksum"));
+    }
+
+    #[test]
+    fn reads_sha256sum_files() {
+        let dir = env::temp_dir().join(format!("aoc-sha256sum-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let checksum_path = dir.join("01.sha256");
+        fs::write(&checksum_path, format!("{}  01.txt\n", sha256("abc"))).unwrap();
+
+        let verification = verify_at(&checksum_path, "abc").unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(verification, Verification::Match);
+    }
+}
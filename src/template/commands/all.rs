@@ -1,206 +1,1577 @@
-use std::io;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{fs, io, process};
 
-use crate::template::{
-    readme_benchmarks::{self, Benchmark},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+use crate::template::benchmark_history::{self, Smoothing};
+use crate::template::logging::{error, info, warning};
+use crate::template::output::parse_part_error;
+use crate::template::readme_benchmarks::{
+    self, Benchmark, Bytes, TotalOptions, UpdateOptions, UpdateStatus, YearBenchmarks,
 };
-use crate::{all_days, Day};
+use crate::template::theme::{strip_ansi, theme};
+use crate::template::{paths, SEED_VAR};
+use crate::{all_days, Day, Part};
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut benchmarks: Vec<Benchmark> = vec![];
+pub(crate) use child_commands::parse_duration;
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+pub fn handle(options: RunOptions, all_options: AllOptions) {
+    let AllOptions {
+        require_all,
+        only,
+        quiet: is_quiet,
+        verbose: is_verbose,
+        no_readme: skip_readme,
+        sort_by_time,
+        no_links,
+        max_answer_width,
+        prebuild,
+        fail_fast,
+        clear_readme,
+        store,
+        total,
+        smoothing,
+        existing_only,
+        precision,
+        all_years,
+        summary_line,
+        prune,
+        header,
+        dry_run,
+        show_samples,
+        group_by_week,
+    } = all_options;
+    let is_release = options.release;
+    let is_timed = options.time;
+    let theme = theme();
+
+    if clear_readme {
+        match readme_benchmarks::clear() {
+            Ok(()) => info!(
+                "{}Successfully cleared the benchmarks from the README.{}",
+                theme.emphasis, theme.reset
+            ),
+            Err(e) => {
+                error!("Failed to clear the benchmarks from the README: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let data_dir = paths::data_dir();
+    let years = if all_years {
+        match namespaced_years(Path::new(&data_dir)) {
+            Ok(years) if !years.is_empty() => years.into_iter().map(Some).collect(),
+            Ok(_) => {
+                error!("No year found in \"{data_dir}\", expecting directories such as \"{data_dir}/2023\".");
+                process::exit(1);
+            }
+            Err(e) => {
+                error!("Failed to list the years in \"{data_dir}\": {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        vec![None]
+    };
+
+    if prebuild {
+        let timer = Instant::now();
+        if let Err(e) = child_commands::prebuild(&options) {
+            error!("Failed to prebuild the solutions: {e}");
+            process::exit(1);
+        }
+        info!(
+            "{}Prebuilt the solutions in {:.2?}.{}\n",
+            theme.emphasis,
+            timer.elapsed(),
+            theme.reset
+        );
+    }
+
+    if let Err(e) = child_commands::handle_interrupts() {
+        warning!("Could not install the Ctrl-C handler: {e}");
+    }
+
+    let wall_clock = Instant::now();
+    let mut runs: Vec<YearRun> = vec![];
+    let mut failures: Vec<(Option<u16>, Day, Error)> = vec![];
+    for (index, year) in years.into_iter().enumerate() {
+        let options = match year {
+            Some(year) => options.clone().year(year),
+            None => options.clone(),
+        };
+        if let Some(year) = year {
+            if index > 0 {
+                println!();
+            }
+            println!("{}Year {year}{}", theme.day, theme.reset);
+            println!("========\n");
+        }
+
+        let days = match selected_days(existing_only, only.as_ref(), year) {
+            Ok(days) => days,
+            Err(e) => {
+                error!(
+                    "Failed to list the solutions in \"{}\": {e}",
+                    paths::bin_dir()
+                );
+                process::exit(1);
+            }
+        };
+        let total_days = days.len();
+        let (results, errors) = run_days(days, fail_fast, |index, day| {
+            run_listed_day(index, day, &options, max_answer_width, total_days, is_quiet)
+        });
+
+        if fail_fast {
+            // the error was already printed with the output of the day.
+            if let Some((day, _)) = errors.first() {
+                error!("\nStopped after day {day} failed, as `--fail-fast` is set.");
+                process::exit(1);
+            }
+        }
+
+        failures.extend(errors.into_iter().map(|(day, e)| (year, day, e)));
+        runs.push(YearRun {
+            year,
+            benchmarks: results.into_iter().flatten().collect(),
+            total_days,
+        });
+    }
+
+    let total_days: usize = runs.iter().map(|run| run.total_days).sum();
+    let solved_days = runs
+        .iter()
+        .flat_map(|run| &run.benchmarks)
+        .filter(|bench| !bench.timed_out)
+        .count();
+    let summary = summary_line
+        .then(|| format_summary(runs.iter().flat_map(|run| &run.benchmarks), failures.len()));
+
+    if is_timed {
+        let total_millis = runs
+            .iter()
+            .flat_map(|run| &run.benchmarks)
+            .map(|x| x.total_nanos)
+            .sum::<f64>()
+            / 1_000_000_f64;
+
+        println!();
+        if !total.hidden {
+            println!(
+                "{}{}:{} {}{}{}",
+                theme.label,
+                total.label("Total"),
+                theme.reset,
+                theme.emphasis,
+                total.format(total_millis),
+                theme.reset
+            );
+        }
+        // the measured total leaves out building and spawning the solutions.
+        println!(
+            "{}Wall-clock:{} {:.2?}",
+            theme.label,
+            theme.reset,
+            wall_clock.elapsed()
+        );
+    }
+
+    // the history does not record the year of the days.
+    let (store, smoothing) = if all_years && (store || smoothing.is_some()) {
+        warning!("The benchmark history does not record years, `--store` and `--smooth` are ignored with `--all-years`.");
+        (false, None)
+    } else {
+        (store, smoothing)
+    };
+
+    // the history is read before the benchmarks of this run are stored, so that they are not
+    // averaged twice.
+    let history = smoothing
+        .filter(|_| is_timed && !options.example)
+        .and_then(|smoothing| match benchmark_history::read() {
+            Ok(history) => Some((history, smoothing)),
+            Err(benchmark_history::Error::IO(e)) if e.kind() == io::ErrorKind::NotFound => {
+                Some((vec![], smoothing))
+            }
+            Err(e) => {
+                warning!("Could not read the benchmark history, the timings are not smoothed: {e}");
+                None
+            }
+        });
+
+    if store {
+        let benchmarks: Vec<Benchmark> =
+            runs.iter().flat_map(|run| run.benchmarks.clone()).collect();
+        if !is_timed || options.example {
+            warning!("Only timed runs against the puzzle inputs are stored, pass `--time` without `--examples`.");
+        } else if let Err(e) = benchmark_history::append(&benchmarks) {
+            error!("Failed to store the benchmarks: {e}");
+        } else {
+            info!(
+                "{}Stored the benchmarks in \"{}\".{}",
+                theme.emphasis,
+                paths::history_path(),
+                theme.reset
+            );
+        }
+    }
+
+    // timings of the examples are meaningless in the README.
+    if is_release && !skip_readme && !options.example {
+        println!();
+
+        let update_options = UpdateOptions {
+            verbose: is_verbose,
+            sort_by_time,
+            no_links,
+            total,
+            precision,
+            header,
+            show_samples,
+            group_by_week,
+            ..Default::default()
+        };
+        let status = if all_years {
+            let years = runs
+                .into_iter()
+                .filter_map(|run| {
+                    let total_millis =
+                        run.benchmarks.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+                    Some(YearBenchmarks {
+                        year: run.year?,
+                        benchmarks: run.benchmarks,
+                        total_millis,
+                    })
+                })
+                .collect();
+            if dry_run {
+                readme_benchmarks::update_years_dry_run(years, &update_options)
+                    .and_then(|updated| readme_benchmarks::print_changes(&updated))
+                    .map(|()| None)
+            } else {
+                readme_benchmarks::update_years(years, &update_options).map(Some)
+            }
+        } else {
+            let mut benchmarks = runs.pop().map(|run| run.benchmarks).unwrap_or_default();
+
+            if let Some((history, smoothing)) = history {
+                benchmark_history::smooth(&mut benchmarks, &history, smoothing);
+            }
+
+            if only.is_some() || options.part.is_some() {
+                benchmarks = merge_with_readme(benchmarks, options.part);
+                check_stale_rows(&mut benchmarks, prune);
+            }
+
+            let total_millis =
+                benchmarks.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+            if dry_run {
+                readme_benchmarks::update_dry_run(benchmarks, total_millis, &update_options)
+                    .and_then(|updated| readme_benchmarks::print_changes(&updated))
+                    .map(|()| None)
+            } else {
+                readme_benchmarks::update(benchmarks, total_millis, &update_options).map(Some)
+            }
+        };
+
+        match status {
+            Ok(None) => {
+                info!(
+                    "{}Dry run, the README was left untouched.{}",
+                    theme.emphasis, theme.reset
+                )
+            }
+            Ok(Some(UpdateStatus::Written)) => {
+                info!(
+                    "{}Successfully updated README with benchmarks.{}",
+                    theme.emphasis, theme.reset
+                )
+            }
+            Ok(Some(UpdateStatus::Unchanged)) => {
+                info!(
+                    "{}README already up to date.{}",
+                    theme.emphasis, theme.reset
+                )
+            }
+            Err(e) => {
+                error!("Failed to update readme with benchmarks: {e}");
+            }
+        }
+    }
+
+    println!(
+        "\n{}Solved:{} {solved_days}/{total_days} days",
+        theme.label, theme.reset
+    );
+
+    if !failures.is_empty() {
+        println!("\n{}Failed:{}", theme.label, theme.reset);
+        for (year, day, e) in &failures {
+            match year {
+                Some(year) => println!("Day {day} of {year}: {e}"),
+                None => println!("Day {day}: {e}"),
+            }
+        }
+    }
+
+    if let Some(summary) = summary {
+        println!("\n{summary}");
+    }
+
+    if require_all && solved_days < total_days {
+        error!("{} day(s) not solved.", total_days - solved_days);
+        process::exit(1);
+    }
+
+    if !failures.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Formats the machine-readable summary of a run of the `all` command, e.g.
+/// `SUMMARY solved=18 errored=1 timeout=0 total_ms=1234.56`.
+///
+/// The format is stable so that scripts can rely on it: the `SUMMARY` prefix followed by the
+/// `solved`, `errored` and `timeout` day counts and the total time of the solved days in
+/// milliseconds with two decimals, as space-separated `key=value` pairs in this order.
+pub fn format_summary<'a>(
+    benchmarks: impl IntoIterator<Item = &'a Benchmark>,
+    errored: usize,
+) -> String {
+    let (mut solved, mut timeout, mut total_nanos) = (0, 0, 0_f64);
+    for bench in benchmarks {
+        if bench.timed_out {
+            timeout += 1;
+        } else {
+            solved += 1;
+            total_nanos += bench.total_nanos;
+        }
+    }
+
+    format!(
+        "SUMMARY solved={solved} errored={errored} timeout={timeout} total_ms={:.2}",
+        total_nanos / 1_000_000_f64
+    )
+}
+
+/// The days that ran for a year, or for the only year of the repository.
+struct YearRun {
+    year: Option<u16>,
+    benchmarks: Vec<Benchmark>,
+    total_days: usize,
+}
+
+/// The days to run: the days that have a solution file with `existing_only`, every day otherwise,
+/// restricted to the days selected by `only`.
+fn selected_days(
+    existing_only: bool,
+    only: Option<&DayFilter>,
+    year: Option<u16>,
+) -> io::Result<Vec<Day>> {
+    let days = if existing_only {
+        existing_days(
+            Path::new(&paths::bin_dir()),
+            &paths::bin_name_pattern(),
+            year,
+        )?
+    } else {
+        all_days().collect()
+    };
+
+    Ok(days
+        .into_iter()
+        .filter(|day| only.is_none_or(|filter| filter.contains(*day)))
+        .collect())
+}
+
+/// Runs the `index`-th day of the `all` command, printing its header and its output.
+fn run_listed_day(
+    index: usize,
+    day: Day,
+    options: &RunOptions,
+    max_answer_width: Option<usize>,
+    total_days: usize,
+    is_quiet: bool,
+) -> Result<Option<Benchmark>, Error> {
+    let theme = theme();
+    let progress = Progress::new(total_days, is_quiet);
+
+    if index > 0 {
+        println!();
+    }
+
+    println!("{}Day {day}{}", theme.day, theme.reset);
+    println!("------");
+
+    progress.show(index + 1, day);
+    let result = match run_day_with(
+        day,
+        options,
+        |line| {
+            progress.clear();
+            match max_answer_width {
+                Some(width) => println!("{}", truncate_answer(line, width)),
+                None => println!("{line}"),
+            }
+        },
+        move |line| {
+            progress.clear();
+            eprintln!("{line}");
+        },
+    ) {
+        Err(Error::TimedOut) => {
+            progress.clear();
+            println!("{}Timed out.{}", theme.error, theme.reset);
+            Ok(Some(Benchmark {
+                timed_out: true,
+                ..child_commands::empty_bench(day)
+            }))
+        }
+        result => result,
+    };
+    progress.clear();
+
+    if child_commands::is_interrupted() {
+        // the output of the interrupted day is incomplete, leave the README untouched.
+        error!("\nInterrupted, the README was not updated.");
+        process::exit(130);
+    }
+
+    match &result {
+        Ok(Some(_)) => {}
+        Ok(None) => println!("Not solved."),
+        Err(e) => println!("{}Failed: {e}{}", theme.error, theme.reset),
+    }
+
+    result
+}
+
+/// Runs every day in order, collecting the results of the days that ran and the errors of the
+/// days that failed. With `fail_fast`, no day runs after the first failing day.
+fn run_days<T>(
+    days: Vec<Day>,
+    fail_fast: bool,
+    mut run: impl FnMut(usize, Day) -> Result<T, Error>,
+) -> (Vec<T>, Vec<(Day, Error)>) {
+    let mut results = vec![];
+    let mut errors = vec![];
+
+    for (index, day) in days.into_iter().enumerate() {
+        match run(index, day) {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                errors.push((day, e));
+                if fail_fast {
+                    break;
+                }
+            }
+        }
+    }
+
+    (results, errors)
+}
+
+/// The path of the solution of a day, prefixed with `./`.
+#[deprecated(note = "use `paths::bin_path` instead")]
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    readme_benchmarks::get_path_for_bin(day)
+}
+
+/// Runs the solution of a single day under a sampling profiler (`samply` or `perf`, whichever is
+/// installed) and saves the profile in the `profiles` directory.
+pub fn profile(day: Day, options: &RunOptions) {
+    if !Path::new(&options.bin_path(day)).exists() {
+        error!("Day {day} is not scaffolded yet.");
+        process::exit(1);
+    }
+
+    match child_commands::profile_solution(day, options) {
+        Ok(Some(path)) => info!("🎄 Saved profile of day {day} to \"{path}\"."),
+        Ok(None) => {
+            error!("No profiler found. Install samply with \"cargo install --locked samply\" or perf with your package manager (e.g. \"apt install linux-perf\").");
+            process::exit(1);
+        }
+        Err(e) => {
+            error!("Failed to profile day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Options of the `all` command, on top of the [`RunOptions`] used to run each day.
+#[derive(Debug, Clone, Default)]
+pub struct AllOptions {
+    /// Exits with a non-zero status code if some days are not solved.
+    pub require_all: bool,
+    /// Only runs the selected days.
+    pub only: Option<DayFilter>,
+    /// Hides the progress indicator.
+    pub quiet: bool,
+    /// Prints the lines of the README table that changed.
+    pub verbose: bool,
+    /// Leaves the README untouched.
+    pub no_readme: bool,
+    /// Appends a ranking of the slowest days to the README.
+    pub sort_by_time: bool,
+    /// Displays the days of the README table as plain text instead of links to their solution.
+    pub no_links: bool,
+    /// Truncates the displayed answers to this number of characters.
+    pub max_answer_width: Option<usize>,
+    /// Builds every solution before running the first day, so that no day pays the compilation.
+    pub prebuild: bool,
+    /// Stops at the first day that fails to run, instead of running the remaining days and
+    /// listing the failures at the end.
+    pub fail_fast: bool,
+    /// Removes the benchmarking table from the README instead of running the days.
+    pub clear_readme: bool,
+    /// Appends the benchmarks to the history file.
+    pub store: bool,
+    /// How the total time is displayed, in the output and in the README.
+    pub total: TotalOptions,
+    /// Writes the exponential moving average of the recent runs of each day to the README instead
+    /// of the timings of this run.
+    pub smoothing: Option<Smoothing>,
+    /// Only runs the days that have a solution file, instead of listing every day.
+    pub existing_only: bool,
+    /// Rounds the timings of the README table to this number of significant figures.
+    pub precision: Option<usize>,
+    /// Runs the days of every year namespace of the data directory, e.g. `data/2023`, and writes
+    /// one table per year to the README.
+    pub all_years: bool,
+    /// Prints a machine-readable summary as the last line of the output, see [`format_summary`].
+    pub summary_line: bool,
+    /// Removes the rows of the README table whose solution file no longer exists, instead of
+    /// warning about them.
+    pub prune: bool,
+    /// The title of the section header of the README table, `Benchmarks` by default.
+    pub header: Option<String>,
+    /// Prints the changes the benchmarks would make to the README table instead of writing it.
+    pub dry_run: bool,
+    /// Shows the number of samples of each part next to its timing in the README table.
+    pub show_samples: bool,
+    /// Splits the README table into weeks, each with the subtotal of its days.
+    pub group_by_week: bool,
+}
+
+/// Options used when running the solution of a day.
+///
+/// The default options run both parts of a debug build once against the puzzle input. Options are
+/// set with the builder methods, e.g. `RunOptions::default().release(true).time(true)`.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Runs an optimized build of the solution.
+    pub release: bool,
+    /// Benchmarks the solution instead of running it once.
+    pub time: bool,
+    /// Runs exactly this number of samples when benchmarking instead of scaling them with the
+    /// execution time.
+    pub samples: Option<u32>,
+    /// Keeps sampling until the samples ran for at least this duration when benchmarking, unless
+    /// an exact number of `samples` is set.
+    pub min_time: Option<Duration>,
+    /// Runs each part against its example file instead of the puzzle input.
+    pub example: bool,
+    /// Kills the solution if it runs for longer than this duration.
+    pub timeout: Option<Duration>,
+    /// Only runs this part of the solution.
+    pub part: Option<Part>,
+    /// Passes this seed to the solution through the [`SEED_VAR`] environment variable, see
+    /// [`crate::template::seed`].
+    pub seed: Option<u64>,
+    /// Runs the solution of this year in a repository holding several years, reading its data
+    /// from the namespace of the year, see [`paths::year_data_dir`].
+    pub year: Option<u16>,
+}
+
+impl RunOptions {
+    /// Sets [`RunOptions::release`].
+    #[must_use]
+    pub fn release(mut self, release: bool) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Sets [`RunOptions::time`].
+    #[must_use]
+    pub fn time(mut self, time: bool) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Sets [`RunOptions::samples`].
+    #[must_use]
+    pub fn samples(mut self, samples: u32) -> Self {
+        self.samples = Some(samples);
+        self
+    }
+
+    /// Sets [`RunOptions::min_time`].
+    #[must_use]
+    pub fn min_time(mut self, min_time: Duration) -> Self {
+        self.min_time = Some(min_time);
+        self
+    }
+
+    /// Sets [`RunOptions::example`].
+    #[must_use]
+    pub fn example(mut self, example: bool) -> Self {
+        self.example = example;
+        self
+    }
+
+    /// Sets [`RunOptions::timeout`].
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets [`RunOptions::part`].
+    #[must_use]
+    pub fn part(mut self, part: Part) -> Self {
+        self.part = Some(part);
+        self
+    }
+
+    /// Sets [`RunOptions::seed`].
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets [`RunOptions::year`].
+    #[must_use]
+    pub fn year(mut self, year: u16) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// The environment variables set on the invocations of the solution.
+    pub(crate) fn child_envs(&self) -> Vec<(&'static str, String)> {
+        let mut envs = vec![];
+        if let Some(seed) = self.seed {
+            envs.push((SEED_VAR, seed.to_string()));
+        }
+        if let Some(year) = self.year {
+            envs.push((
+                "AOC_DATA_DIR",
+                paths::year_data_dir(&paths::data_dir(), year),
+            ));
+            envs.push(("AOC_YEAR", year.to_string()));
+        }
+        envs
+    }
+
+    /// The name of the cargo binary of the solution of a day, following [`RunOptions::year`].
+    fn bin_name(&self, day: Day) -> String {
+        match self.year {
+            Some(year) => paths::year_bin_name(day, year),
+            None => paths::bin_name(day),
+        }
+    }
+
+    /// The path of the solution of a day, following [`RunOptions::year`].
+    fn bin_path(&self, day: Day) -> String {
+        match self.year {
+            Some(year) => paths::year_bin_path(day, year),
+            None => paths::bin_path(day),
+        }
+    }
+}
+
+/// Runs the solution of a day and returns its parsed [`Benchmark`] without printing its standard
+/// output. Its standard error, e.g. the errors of its parts, is still forwarded to stderr.
+/// Returns [`None`] if the day has not been scaffolded yet, and [`Error::TimedOut`] if the solution
+/// was killed after running for longer than [`RunOptions::timeout`].
+pub fn run_day(day: Day, options: &RunOptions) -> Result<Option<Benchmark>, Error> {
+    run_day_with(day, options, |_| {}, |line| eprintln!("{line}"))
+}
+
+/// Runs the solution of a day and returns every line of its standard output without printing it.
+/// The output is empty if the day has not been scaffolded yet, see [`parse_output`] to get its
+/// [`Benchmark`].
+pub fn run_day_output(day: Day, options: &RunOptions) -> Result<Vec<String>, Error> {
+    child_commands::run_solution(day, options, false, |_| {}, |line| eprintln!("{line}"))
+        .map(|output| output.stdout)
+}
+
+/// The lines printed by a solution, see [`run_day_captured`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedOutput {
+    /// The lines of the standard output.
+    pub stdout: Vec<String>,
+    /// The lines of the standard error, only collected by [`run_day_captured`].
+    pub stderr: Vec<String>,
+}
+
+/// Same as [`run_day_output`], but also collects the lines of the standard error of the solution
+/// (e.g. debug logs) on top of printing them.
+pub fn run_day_captured(day: Day, options: &RunOptions) -> Result<CapturedOutput, Error> {
+    child_commands::run_solution(day, options, true, |_| {}, |line| eprintln!("{line}"))
+}
+
+/// Parses the [`Benchmark`] of a day from the standard output of its solution.
+/// A warning is printed for each part line that could not be parsed, see
+/// [`parse_output_with_warnings`] to handle them instead.
+pub fn parse_output(day: Day, output: &[String]) -> Benchmark {
+    let (bench, warnings) = parse_output_with_warnings(day, output);
+    for warning in warnings {
+        warning!("{warning}");
+    }
+    bench
+}
+
+/// Parses the [`Benchmark`] of a day from the standard output of its solution, along with a
+/// [`ParseWarning`] for each part line that could not be parsed.
+pub fn parse_output_with_warnings(day: Day, output: &[String]) -> (Benchmark, Vec<ParseWarning>) {
+    child_commands::parse_exec_bench(output, day)
+}
+
+/// A part line of the output of a solution that could not be parsed, its part is left out of the
+/// [`Benchmark`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// The timing or the sample count of the line could not be parsed.
+    Timing { line: String },
+    /// The heap allocation of the line could not be parsed.
+    Heap { line: String },
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseWarning::Timing { line } => write!(f, "Could not parse timings from line: {line}"),
+            ParseWarning::Heap { line } => {
+                write!(f, "Could not parse heap allocation from line: {line}")
+            }
+        }
+    }
+}
+
+fn run_day_with(
+    day: Day,
+    options: &RunOptions,
+    mut on_stdout: impl FnMut(&str),
+    on_stderr: impl Fn(&str) + Send + 'static,
+) -> Result<Option<Benchmark>, Error> {
+    let mut bench = None;
+    let mut part_error = None;
+
+    // parse the lines as they are printed, so that only the parts are kept in memory.
+    child_commands::stream_solution(
+        day,
+        options,
+        |line| {
+            on_stdout(line);
+            if part_error.is_none() {
+                part_error = parse_part_error(&strip_ansi(line))
+                    .map(|(part, reason)| format!("part {part} failed: {reason}"));
+            }
+            let bench = bench.get_or_insert_with(|| child_commands::empty_bench(day));
+            if let Some(warning) = child_commands::parse_bench_line(bench, line) {
+                warning!("{warning}");
+            }
+        },
+        on_stderr,
+    )?;
+
+    match part_error {
+        Some(e) => Err(Error::Solution(e)),
+        None => Ok(bench),
+    }
+}
+
+/// Truncates the answer of a `Part N: <answer> (timing) (bytes)` line to `width` characters,
+/// followed by `…`. Other lines and shorter answers are returned unchanged.
+fn truncate_answer(line: &str, width: usize) -> Cow<'_, str> {
+    // only the final result of a part, printed after a carriage return, is visible.
+    let visible = line.rsplit('\r').next().unwrap_or_default();
+
+    let Some((prefix, rest)) = visible.split_once(": ") else {
+        return Cow::Borrowed(line);
+    };
+    if !prefix.starts_with("Part ") {
+        return Cow::Borrowed(line);
+    }
+
+    let mut groups = rest.rsplitn(3, " (");
+    let (Some(bytes), Some(timing), Some(answer)) = (groups.next(), groups.next(), groups.next())
+    else {
+        return Cow::Borrowed(line);
+    };
+
+    let plain_answer = strip_ansi(answer);
+    if plain_answer.chars().count() <= width {
+        return Cow::Borrowed(line);
+    }
+
+    let theme = theme();
+    let truncated: String = plain_answer.chars().take(width).collect();
+    Cow::Owned(format!(
+        "{prefix}: {}{truncated}…{} ({timing} ({bytes}",
+        theme.answer, theme.reset
+    ))
+}
+
+/// A progress indicator written to stderr while the days run.
+/// It is only displayed when stderr is a terminal, so that it never ends up in piped output.
+#[derive(Clone, Copy)]
+struct Progress {
+    total: usize,
+    enabled: bool,
+}
+
+impl Progress {
+    fn new(total: usize, is_quiet: bool) -> Self {
+        Self {
+            total,
+            enabled: !is_quiet && io::stderr().is_terminal(),
+        }
+    }
+
+    fn show(&self, current: usize, day: Day) {
+        if self.enabled {
+            eprint!("\r\x1b[2K[{current:>2}/{}] Day {day}...", self.total);
+            let _ = io::stderr().flush();
+        }
+    }
+
+    fn clear(&self) {
+        if self.enabled {
+            eprint!("\r\x1b[2K");
+            let _ = io::stderr().flush();
+        }
+    }
+}
+
+/// Keeps the rows of the README table for the days that did not run, so that running a subset
+/// of days only updates their own rows. When a single `part` ran, the other part of the days that
+/// ran is kept as well.
+fn merge_with_readme(mut benchmarks: Vec<Benchmark>, part: Option<Part>) -> Vec<Benchmark> {
+    let Ok(existing) = readme_benchmarks::read() else {
+        return benchmarks;
+    };
+
+    let mut merged: Vec<Benchmark> = vec![];
+
+    for mut old in existing {
+        match benchmarks.iter_mut().find(|new| new.day == old.day) {
+            Some(new) => {
+                if let Some(part) = part {
+                    keep_other_part(new, old, part);
+                }
+            }
+            None => {
+                old.total_nanos = part_nanos(&old.part_1) + part_nanos(&old.part_2);
+                merged.push(old);
+            }
+        }
+    }
+
+    merged.extend(benchmarks);
+    merged.sort_by_key(|bench| bench.day);
+    merged
+}
+
+/// The time of a part of a README row in nanoseconds, `0` if the part is missing.
+fn part_nanos(part: &Option<(String, Bytes)>) -> f64 {
+    part.as_ref()
+        .and_then(|(timing, _)| child_commands::parse_duration(timing))
+        .unwrap_or_default()
+}
+
+/// Completes the benchmark of a day where only `part` ran with the timing and sample count of the
+/// other part from its `old` README row.
+fn keep_other_part(new: &mut Benchmark, old: Benchmark, part: Part) {
+    let (new_part, old_part, kept) = if part == 1 {
+        (&mut new.part_2, old.part_2, 1)
+    } else {
+        (&mut new.part_1, old.part_1, 0)
+    };
+    new.total_nanos += part_nanos(&old_part);
+    *new_part = old_part;
+    new.samples[kept] = old.samples[kept];
+}
+
+/// Warns about the rows of the README table whose solution file no longer exists, or removes them
+/// with `prune`.
+fn check_stale_rows(benchmarks: &mut Vec<Benchmark>, prune: bool) {
+    let stale = stale_days(benchmarks, |day| Path::new(&paths::bin_path(day)).exists());
+    if stale.is_empty() {
+        return;
+    }
+
+    let days = stale
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if prune {
+        benchmarks.retain(|bench| !stale.contains(&bench.day));
+        info!(
+            "Removed the rows of day(s) {days} from the README, their solution no longer exists."
+        );
+    } else {
+        warning!("The README lists day(s) {days} whose solution no longer exists, pass `--prune` to remove them.");
+    }
+}
+
+/// Returns the days of `benchmarks` whose solution file does not exist according to `exists`, e.g.
+/// rows of the README table kept for a solution that was deleted since.
+fn stale_days(benchmarks: &[Benchmark], exists: impl Fn(Day) -> bool) -> Vec<Day> {
+    benchmarks
+        .iter()
+        .map(|bench| bench.day)
+        .filter(|day| !exists(*day))
+        .collect()
+}
+
+/// Returns the years that have a namespace in `data_dir`, i.e. a directory named after the year
+/// such as `data/2023`, in order.
+fn namespaced_years(data_dir: &Path) -> io::Result<Vec<u16>> {
+    let mut years = vec![];
+    for entry in fs::read_dir(data_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name();
+        if let Some(year) = name
+            .to_str()
+            .filter(|name| name.len() == 4 && name.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|name| name.parse().ok())
+        {
+            years.push(year);
+        }
+    }
+
+    years.sort_unstable();
+    Ok(years)
+}
+
+/// Returns the days that have a solution file in `bin_dir` following the naming scheme `pattern`,
+/// e.g. `01.rs`, in order. The files of another year than `year`, if set, and other files of the
+/// directory are ignored.
+fn existing_days(bin_dir: &Path, pattern: &str, year: Option<u16>) -> io::Result<Vec<Day>> {
+    let mut days = vec![];
+    for entry in fs::read_dir(bin_dir)? {
+        if let Some(day) = entry?.file_name().to_str().and_then(|file| match year {
+            Some(year) => paths::parse_year_bin_file(pattern, file, &year.to_string()),
+            None => paths::parse_bin_file(pattern, file),
+        }) {
+            days.push(day);
+        }
+    }
+
+    days.sort();
+    Ok(days)
+}
+
+/// A set of days selected with a comma separated list of days or ranges (e.g. `1,3,5-8`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayFilter(BTreeSet<Day>);
+
+impl DayFilter {
+    /// Returns `true` if the filter selects the provided [`Day`].
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+}
+
+impl FromStr for DayFilter {
+    type Err = DayFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+
+        for token in s.split(',').map(str::trim) {
+            let invalid = || DayFilterError(token.to_string());
+
+            match token.split_once('-') {
+                Some((start, end)) => {
+                    let start: Day = start.trim().parse().map_err(|_| invalid())?;
+                    let end: Day = end.trim().parse().map_err(|_| invalid())?;
+                    if start > end {
+                        return Err(invalid());
+                    }
+                    days.extend(all_days().filter(|day| *day >= start && *day <= end));
+                }
+                None => {
+                    days.insert(token.parse().map_err(|_| invalid())?);
+                }
+            }
+        }
+
+        Ok(Self(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DayFilter`].
+#[derive(Debug)]
+pub struct DayFilterError(String);
+
+impl std::error::Error for DayFilterError {}
+
+impl Display for DayFilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day filter `{}`, expecting days between 1 and 25 or ranges like `5-8`",
+            self.0
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BrokenPipe,
+    TimedOut,
+    /// A part of the solution returned an error.
+    Solution(String),
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture the output of the solution."),
+            Error::TimedOut => write!(f, "the solution timed out."),
+            Error::Solution(e) => write!(f, "{e}"),
+            Error::Parser(e) => write!(f, "could not parse the solution output: {e}"),
+            Error::IO(e) => write!(f, "could not run the solution: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// All solutions live in isolated binaries.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+mod child_commands {
+    use super::{CapturedOutput, Error, ParseWarning, RunOptions};
+    use crate::template::logging;
+    use crate::template::output::parse_answer_line;
+    use crate::template::readme_benchmarks::Bytes;
+    use crate::{Day, Part};
+    use std::{
+        fs,
+        io::{BufRead, BufReader},
+        path::Path,
+        process::{Child, Command, Stdio},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc::{self, RecvTimeoutError},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    /// The solution currently running, kept reachable so that the Ctrl-C handler can kill it.
+    static RUNNING_CHILD: Mutex<Option<Child>> = Mutex::new(None);
+    static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+    /// Installs a Ctrl-C handler killing the running solution.
+    /// The interruption is then reported by [`is_interrupted`] once the solution exited.
+    pub fn handle_interrupts() -> Result<(), ctrlc::Error> {
+        ctrlc::set_handler(|| {
+            INTERRUPTED.store(true, Ordering::SeqCst);
+            kill_running_child();
+        })
+    }
+
+    fn kill_running_child() {
+        if let Some(child) = RUNNING_CHILD.lock().unwrap().as_mut() {
+            let _ = child.kill();
+        }
+    }
+
+    /// A thread calling `on_timeout` unless it is stopped before the timeout elapsed.
+    struct Watchdog {
+        stop: mpsc::Sender<()>,
+        thread: JoinHandle<bool>,
+    }
+
+    impl Watchdog {
+        fn start(timeout: Duration, on_timeout: impl FnOnce() + Send + 'static) -> Self {
+            let (stop, stopped) = mpsc::channel();
+            let thread = thread::spawn(move || match stopped.recv_timeout(timeout) {
+                Err(RecvTimeoutError::Timeout) => {
+                    on_timeout();
+                    true
+                }
+                _ => false,
+            });
+
+            Self { stop, thread }
+        }
+
+        /// Stops the watchdog and returns `true` if the timeout elapsed before.
+        fn stop(self) -> bool {
+            let _ = self.stop.send(());
+            self.thread.join().unwrap()
+        }
+    }
+
+    /// Returns `true` if Ctrl-C was pressed since the handler was installed.
+    pub fn is_interrupted() -> bool {
+        INTERRUPTED.load(Ordering::SeqCst)
+    }
+
+    /// Builds the arguments of the `cargo run` invocation of a solution bin.
+    fn cargo_run_args(day: Day, options: &RunOptions) -> Vec<String> {
+        let mut args: Vec<String> = vec!["run".into(), "--quiet".into(), "--bin".into()];
+        args.push(options.bin_name(day));
+
+        if options.release {
+            args.push("--release".into());
+        }
+
+        // mirror `--time` and `--example` flags to child invocations.
+        let mut child_args: Vec<String> = vec![];
+
+        if options.time {
+            child_args.push("--time".into());
+
+            if let Some(samples) = options.samples {
+                child_args.push("--samples".into());
+                child_args.push(samples.to_string());
+            }
+
+            if let Some(min_time) = options.min_time {
+                child_args.push("--min-time".into());
+                child_args.push(min_time.as_millis().to_string());
+            }
+        }
+
+        if options.example {
+            child_args.push("--example".into());
+        }
+
+        if let Some(part) = options.part {
+            child_args.push("--part".into());
+            child_args.push(part.to_string());
+        }
+
+        if !child_args.is_empty() {
+            args.push("--".into());
+            args.extend(child_args);
+        }
+
+        args
+    }
+
+    /// Builds the `cargo run` invocation of a solution bin, see [`cargo_run_args`].
+    fn cargo_run_command(day: Day, options: &RunOptions) -> Command {
+        let args = cargo_run_args(day, options);
+        logging::trace_command("cargo", &args);
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args).envs(options.child_envs());
+        cmd
+    }
+
+    /// A sampling profiler used to record the execution of a solution.
+    #[derive(Clone, Copy)]
+    enum Profiler {
+        Samply,
+        Perf,
+    }
+
+    impl Profiler {
+        /// Returns the first profiler installed on the system.
+        fn detect() -> Option<Self> {
+            [Self::Samply, Self::Perf].into_iter().find(|profiler| {
+                Command::new(profiler.command())
+                    .arg("--version")
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .is_ok_and(|status| status.success())
+            })
+        }
+
+        fn command(self) -> &'static str {
+            match self {
+                Self::Samply => "samply",
+                Self::Perf => "perf",
+            }
+        }
+
+        fn output_path(self, day: Day) -> String {
+            match self {
+                Self::Samply => format!("profiles/{day}.json.gz"),
+                Self::Perf => format!("profiles/{day}.perf.data"),
+            }
+        }
+
+        fn record_args(self, output_path: &str) -> Vec<String> {
+            match self {
+                Self::Samply => vec![
+                    "record".into(),
+                    "--save-only".into(),
+                    "--output".into(),
+                    output_path.into(),
+                    "--".into(),
+                ],
+                Self::Perf => vec![
+                    "record".into(),
+                    "--call-graph".into(),
+                    "dwarf".into(),
+                    "--output".into(),
+                    output_path.into(),
+                    "--".into(),
+                ],
+            }
         }
+    }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    /// Builds every solution bin with suppressed output, the compiler errors are returned on
+    /// failure.
+    pub fn prebuild(options: &RunOptions) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+        if options.release {
+            args.push("--release");
+        }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        logging::trace_command("cargo", &args);
+        let output = Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()?;
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_bench(&output, day);
-            benchmarks.push(val);
+        if !output.status.success() {
+            return Err(Error::Parser(format!(
+                "could not build the solutions\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
         }
-    });
-
-    if is_timed {
-        let total_millis = benchmarks.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        Ok(())
     }
 
-    if is_release {
-        println!();
+    /// Runs the solution bin for a given day under a profiler and returns the path of the saved
+    /// profile, or [`None`] if no profiler is installed.
+    pub fn profile_solution(day: Day, options: &RunOptions) -> Result<Option<String>, Error> {
+        let Some(profiler) = Profiler::detect() else {
+            return Ok(None);
+        };
 
-        let total_millis = benchmarks.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
-        match readme_benchmarks::update(benchmarks, total_millis) {
-            Ok(()) => {
-                println!("{ANSI_ITALIC}Successfully updated README with benchmarks.{ANSI_RESET}")
-            }
-            Err(_) => {
-                eprintln!("Failed to update readme with benchmarks.");
-            }
+        // build beforehand, so that the compilation does not end up in the profile.
+        let mut build_args = vec!["build", "--quiet", "--bin"];
+        let bin_name = options.bin_name(day);
+        build_args.push(&bin_name);
+        if options.release {
+            build_args.push("--release");
         }
-    }
-}
-
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
-}
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
+        logging::trace_command("cargo", &build_args);
+        let status = Command::new("cargo").args(&build_args).status()?;
+        if !status.success() {
+            return Err(Error::Parser(format!("could not build day {day}")));
+        }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+        fs::create_dir_all("profiles")?;
+        let output_path = profiler.output_path(day);
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+        let mut args = profiler.record_args(&output_path);
+        args.push("cargo".into());
+        args.extend(cargo_run_args(day, options));
+        logging::trace_command(profiler.command(), &args);
+        let status = Command::new(profiler.command())
+            .args(&args)
+            .envs(options.child_envs())
+            .status()?;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !status.success() {
+            return Err(Error::Parser(format!(
+                "{} exited with a non-zero status",
+                profiler.command()
+            )));
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        Ok(Some(output_path))
+    }
+
+    /// Run the solution bin for a given day, forwarding each line of its output to the hooks and
+    /// collecting the lines of its stdout, as well as the lines of its stderr with `capture_stderr`.
+    pub fn run_solution(
+        day: Day,
+        options: &RunOptions,
+        capture_stderr: bool,
+        mut on_stdout: impl FnMut(&str),
+        on_stderr: impl Fn(&str) + Send + 'static,
+    ) -> Result<CapturedOutput, Error> {
+        let mut stdout = vec![];
+        let stderr = Arc::new(Mutex::new(vec![]));
+        let on_stderr = capture_lines(capture_stderr.then(|| Arc::clone(&stderr)), on_stderr);
 
-        if is_release {
-            args.push("--release");
-        }
+        stream_solution(
+            day,
+            options,
+            |line| {
+                on_stdout(line);
+                stdout.push(line.to_string());
+            },
+            on_stderr,
+        )?;
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let stderr = std::mem::take(&mut *stderr.lock().unwrap());
+        Ok(CapturedOutput { stdout, stderr })
+    }
+
+    /// Wraps a hook so that the lines it receives are also pushed to `lines`, if provided.
+    fn capture_lines(
+        lines: Option<Arc<Mutex<Vec<String>>>>,
+        hook: impl Fn(&str) + Send + 'static,
+    ) -> impl Fn(&str) + Send + 'static {
+        move |line| {
+            if let Some(lines) = &lines {
+                lines.lock().unwrap().push(line.to_string());
+            }
+            hook(line);
         }
+    }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    /// Run the solution bin for a given day, forwarding each line of its output to the hooks
+    /// without retaining it.
+    pub fn stream_solution(
+        day: Day,
+        options: &RunOptions,
+        mut on_stdout: impl FnMut(&str),
+        on_stderr: impl Fn(&str) + Send + 'static,
+    ) -> Result<(), Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&options.bin_path(day)).exists() {
+            return Ok(());
+        }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        // spawn child command with piped stdout/stderr and forward output to the hooks.
+        let mut cmd = cargo_run_command(day, options)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
+        *RUNNING_CHILD.lock().unwrap() = Some(cmd);
+        let watchdog = options
+            .timeout
+            .map(|timeout| Watchdog::start(timeout, kill_running_child));
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                on_stderr(&line.unwrap());
             });
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            on_stdout(&line.unwrap());
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
 
-        Ok(output)
+        // take the child out of the lock before waiting, so that the Ctrl-C handler never blocks.
+        let child = RUNNING_CHILD.lock().unwrap().take();
+        if let Some(mut child) = child {
+            child.wait()?;
+        }
+
+        if watchdog.is_some_and(Watchdog::stop) {
+            return Err(Error::TimedOut);
+        }
+
+        Ok(())
     }
 
-    pub fn parse_exec_bench(output: &[String], day: Day) -> super::Benchmark {
-        let mut bench = super::Benchmark {
+    /// Parses the benchmark of a day from its output, along with the warnings of the part lines
+    /// that could not be parsed.
+    pub fn parse_exec_bench(output: &[String], day: Day) -> (super::Benchmark, Vec<ParseWarning>) {
+        let mut bench = empty_bench(day);
+        let warnings = output
+            .iter()
+            .filter_map(|line| parse_bench_line(&mut bench, line))
+            .collect();
+        (bench, warnings)
+    }
+
+    /// A benchmark of a day without any solved part.
+    pub fn empty_bench(day: Day) -> super::Benchmark {
+        super::Benchmark {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            timed_out: false,
+            samples: [None; 2],
+        }
+    }
+
+    /// Adds a line of the solution output to the benchmark, lines that are not parts are ignored.
+    /// Returns a warning if the line is a part line that could not be parsed.
+    pub fn parse_bench_line(bench: &mut super::Benchmark, l: &str) -> Option<ParseWarning> {
+        let line = match parse_line(l) {
+            Ok(Some(line)) => line,
+            Ok(None) => return None,
+            Err(warning) => return Some(warning),
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                let part = l.split(':').next()?;
-                let Some(heap_allocation) = parse_heap_allocation(l) else {
-                    eprintln!("Could not parse heap allocation from line: {l}");
-                    return None;
-                };
-
-                if !l.contains(" samples)") {
-                    return Some((part, "", 0_f64, heap_allocation));
-                }
+        let part = Some((line.timing.into(), line.heap_allocation));
+        let (slot, samples) = match line.part.into_inner() {
+            1 => (&mut bench.part_1, &mut bench.samples[0]),
+            _ => (&mut bench.part_2, &mut bench.samples[1]),
+        };
+        *slot = part;
+        *samples = line.samples;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        bench.total_nanos += line.nanos;
+        None
+    }
 
-                Some((part, timing_str, nanos, heap_allocation))
-            })
-            .for_each(|(part, timing_str, nanos, heap_allocation)| {
-                if part.contains("Part 1") {
-                    bench.part_1 = Some((timing_str.into(), heap_allocation.into()));
-                } else if part.contains("Part 2") {
-                    bench.part_2 = Some((timing_str.into(), heap_allocation.into()));
-                }
+    /// A part line of the solution output, e.g. `Part 1: 42 (74.13ns @ 100 samples) (10KB)`.
+    struct PartLine<'a> {
+        part: Part,
+        timing: &'a str,
+        nanos: f64,
+        heap_allocation: Bytes,
+        samples: Option<u128>,
+    }
 
-                bench.total_nanos += nanos;
-            });
+    /// Parses a part line of the solution output.
+    /// Returns `Ok(None)` for lines without a result (unsolved parts or any other output), and a
+    /// warning naming the field that could not be parsed for malformed part lines.
+    fn parse_line(l: &str) -> Result<Option<PartLine<'_>>, ParseWarning> {
+        let Some((part, _)) = parse_answer_line(l) else {
+            return Ok(None);
+        };
+
+        if !l.contains(" samples)") {
+            // the heap allocation is only measured by timed runs.
+            return Ok(Some(PartLine {
+                part,
+                timing: "",
+                nanos: 0_f64,
+                heap_allocation: parse_heap_allocation(l).unwrap_or(Bytes(0)),
+                samples: None,
+            }));
+        }
+
+        // the heap allocation is only printed when the counting allocator is installed.
+        let heap_allocation = if l.trim_end().ends_with(" samples)") {
+            Bytes(0)
+        } else {
+            parse_heap_allocation(l).ok_or_else(|| ParseWarning::Heap {
+                line: l.to_string(),
+            })?
+        };
+        let timing_warning = || ParseWarning::Timing {
+            line: l.to_string(),
+        };
+        let samples = parse_samples(l).ok_or_else(timing_warning)?;
+        let (timing, nanos) = parse_time(l).ok_or_else(timing_warning)?;
+
+        Ok(Some(PartLine {
+            part,
+            timing,
+            nanos,
+            heap_allocation,
+            samples: Some(samples),
+        }))
+    }
 
-        bench
+    fn parse_samples(line: &str) -> Option<u128> {
+        line.split(" samples)")
+            .next()?
+            .rsplit('@')
+            .next()?
+            .trim()
+            .replace(['_', ','], "")
+            .parse()
+            .ok()
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parses a number, ignoring `_` and `,` grouping characters.
+    /// Only `.` is treated as the decimal point, so a decimal comma is not supported.
+    fn parse_to_float(s: &str) -> Option<f64> {
+        s.replace(['_', ','], "").parse().ok()
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_duration(str_timing).filter(|nanos| *nanos >= 0_f64)?;
 
         Some((str_timing, parsed_timing))
     }
 
-    fn parse_heap_allocation(line: &str) -> Option<&str> {
-        let str_heap_allocation = line.split(") (").last()?.split(')').next()?.trim();
-        byte_unit::Byte::parse_str(str_heap_allocation, true).ok()?;
+    /// Parses a duration formatted with [`std::fmt::Debug`] into nanoseconds.
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        // the unit is the trailing run of letters, so that an exponent (e.g. `1.5e3ns`) stays
+        // part of the number.
+        let s = s.trim();
+        let (number, unit) = s.split_at(s.trim_end_matches(char::is_alphabetic).len());
+
+        let factor = match unit {
+            "ns" => 1_f64,
+            // MICRO SIGN (U+00B5) as printed by `Duration`, or GREEK SMALL LETTER MU (U+03BC).
+            "\u{b5}s" | "\u{3bc}s" => 1000_f64,
+            "ms" => 1_000_000_f64,
+            "s" => 1_000_000_000_f64,
+            _ => return None,
+        };
+
+        parse_to_float(number).map(|x| x * factor)
+    }
+
+    /// Parses the heap allocation printed in the last parenthesized group of a part line.
+    /// Decimal (`KB`, `MB`) and binary (`KiB`, `MiB`) units are accepted, with or without a space
+    /// between the value and the unit, e.g. `(1.5 KiB)`, `(1.5KB)` or `(0 B)`.
+    fn parse_heap_allocation(line: &str) -> Option<Bytes> {
+        let group = &line[line.rfind('(')? + 1..];
+        let str_heap_allocation: String = group
+            .split(')')
+            .next()?
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        if !str_heap_allocation.ends_with(['B', 'b']) {
+            return None;
+        }
 
-        Some(str_heap_allocation)
+        str_heap_allocation.parse().ok()
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -221,9 +1592,273 @@ mod child_commands {
     #[cfg(test)]
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_bench;
+        use super::super::{
+            run_days, stale_days, truncate_answer, Error, ParseWarning, RunOptions,
+        };
+        use super::{
+            capture_lines, cargo_run_args, cargo_run_command, empty_bench, parse_exec_bench,
+            parse_heap_allocation, Watchdog,
+        };
+        use crate::template::readme_benchmarks::Bytes;
+        use crate::template::theme::strip_ansi;
+        use crate::template::{logging, paths};
+        use std::process::Command;
+        use std::sync::{Arc, Mutex};
+        use std::thread;
+        use std::time::{Duration, Instant};
+
+        use crate::{day, part};
+
+        #[test]
+        fn test_cargo_run_args() {
+            let options = RunOptions {
+                release: true,
+                time: true,
+                samples: Some(10),
+                min_time: Some(Duration::from_millis(500)),
+                example: true,
+                timeout: None,
+                part: Some(part!(2)),
+                seed: None,
+                year: None,
+            };
+            assert_eq!(
+                cargo_run_args(day!(3), &options),
+                [
+                    "run",
+                    "--quiet",
+                    "--bin",
+                    "03",
+                    "--release",
+                    "--",
+                    "--time",
+                    "--samples",
+                    "10",
+                    "--min-time",
+                    "500",
+                    "--example",
+                    "--part",
+                    "2"
+                ]
+            );
+            assert_eq!(
+                cargo_run_args(day!(3), &RunOptions::default()),
+                ["run", "--quiet", "--bin", "03"]
+            );
+        }
+
+        #[test]
+        fn test_traces_child_commands() {
+            let args = cargo_run_args(day!(3), &RunOptions::default().release(true).time(true));
+
+            logging::set_verbosity(logging::VERBOSE);
+            assert_eq!(logging::trace_command("cargo", &args), None);
+
+            logging::set_verbosity(logging::VERY_VERBOSE);
+            assert_eq!(
+                logging::trace_command("cargo", &args).as_deref(),
+                Some("Running `cargo run --quiet --bin 03 --release -- --time`")
+            );
+            assert_eq!(
+                logging::command_line("samply", &["record", "--output", "my profile.json"]),
+                "samply record --output \"my profile.json\""
+            );
+
+            logging::set_verbosity(0);
+        }
+
+        #[test]
+        fn test_passes_envs_to_child() {
+            let envs = |options: &RunOptions| {
+                cargo_run_command(day!(3), options)
+                    .get_envs()
+                    .map(|(key, value)| (key.to_owned(), value.map(ToOwned::to_owned)))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(
+                envs(&RunOptions::default().seed(42)),
+                [("AOC_SEED".into(), Some("42".into()))]
+            );
+            assert!(envs(&RunOptions::default()).is_empty());
+            assert_eq!(
+                envs(&RunOptions::default().year(2022)),
+                [
+                    (
+                        "AOC_DATA_DIR".into(),
+                        Some(paths::year_data_dir(&paths::data_dir(), 2022).into())
+                    ),
+                    ("AOC_YEAR".into(), Some("2022".into())),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_run_options_builder() {
+            let options = RunOptions::default()
+                .release(true)
+                .time(true)
+                .samples(10)
+                .min_time(Duration::from_millis(500))
+                .example(true)
+                .part(part!(2));
+            let expected = RunOptions {
+                release: true,
+                time: true,
+                samples: Some(10),
+                min_time: Some(Duration::from_millis(500)),
+                example: true,
+                timeout: None,
+                part: Some(part!(2)),
+                seed: None,
+                year: None,
+            };
+            assert_eq!(
+                cargo_run_args(day!(3), &options),
+                cargo_run_args(day!(3), &expected)
+            );
+            assert_eq!(
+                RunOptions::default()
+                    .timeout(Duration::from_secs(1))
+                    .timeout,
+                Some(Duration::from_secs(1))
+            );
+        }
+
+        #[test]
+        fn test_capture_lines() {
+            let forwarded = Arc::new(Mutex::new(vec![]));
+            let captured = Arc::new(Mutex::new(vec![]));
+
+            let hook = {
+                let forwarded = Arc::clone(&forwarded);
+                move |line: &str| forwarded.lock().unwrap().push(line.to_string())
+            };
+            let capture = capture_lines(Some(Arc::clone(&captured)), hook.clone());
+            capture("debug: 1");
+            capture("debug: 2");
+            assert_eq!(*captured.lock().unwrap(), ["debug: 1", "debug: 2"]);
+            assert_eq!(*forwarded.lock().unwrap(), ["debug: 1", "debug: 2"]);
+
+            let echo = capture_lines(None, hook);
+            echo("debug: 3");
+            assert_eq!(captured.lock().unwrap().len(), 2);
+            assert_eq!(forwarded.lock().unwrap().len(), 3);
+        }
+
+        #[test]
+        fn test_watchdog_kills_on_timeout() {
+            let child = Command::new("sleep").arg("10").spawn().unwrap();
+            let child = Arc::new(Mutex::new(child));
+
+            let start = Instant::now();
+            let watchdog = Watchdog::start(Duration::from_millis(100), {
+                let child = Arc::clone(&child);
+                move || child.lock().unwrap().kill().unwrap()
+            });
+            // the watchdog locks the child to kill it, so poll instead of blocking on `wait`.
+            while child.lock().unwrap().try_wait().unwrap().is_none() {
+                thread::sleep(Duration::from_millis(10));
+            }
 
-        use crate::day;
+            assert!(watchdog.stop());
+            assert!(start.elapsed() < Duration::from_secs(5));
+        }
+
+        #[test]
+        fn test_watchdog_stopped_in_time() {
+            let watchdog = Watchdog::start(Duration::from_secs(10), || panic!("timed out"));
+            assert!(!watchdog.stop());
+        }
+
+        #[test]
+        fn test_truncate_answer() {
+            let line = "Part 1: \x1b[1m0123456789\x1b[0m (74.13ns @ 100 samples) (10KB)";
+            assert_eq!(
+                strip_ansi(&truncate_answer(line, 4)),
+                "Part 1: 0123… (74.13ns @ 100 samples) (10KB)"
+            );
+            assert_eq!(truncate_answer(line, 10), line);
+
+            let line = "Part 2: 0123\rPart 2: 0123 (1.0µs) (0 B)";
+            assert_eq!(
+                strip_ansi(&truncate_answer(line, 2)),
+                "Part 2: 01… (1.0µs) (0 B)"
+            );
+
+            assert_eq!(
+                truncate_answer("Part 1: ✖             ", 2),
+                "Part 1: ✖             "
+            );
+            assert_eq!(
+                truncate_answer("debug: 0123 (a) (b)", 2),
+                "debug: 0123 (a) (b)"
+            );
+        }
+
+        fn run_stub_days(fail_fast: bool) -> (Vec<u8>, Vec<String>, Vec<u8>) {
+            let mut ran = vec![];
+            let (results, errors) =
+                run_days(vec![day!(1), day!(2), day!(3)], fail_fast, |_, day| {
+                    ran.push(day.into_inner());
+                    match day.into_inner() {
+                        2 => Err(Error::Parser("stub failure".into())),
+                        n => Ok(n),
+                    }
+                });
+            let errors = errors
+                .iter()
+                .map(|(day, e)| format!("{day}: {e}"))
+                .collect();
+            (results, errors, ran)
+        }
+
+        #[test]
+        fn test_run_days_keep_going() {
+            let (results, errors, ran) = run_stub_days(false);
+            assert_eq!(results, vec![1, 3]);
+            assert_eq!(
+                errors,
+                vec!["02: could not parse the solution output: stub failure"]
+            );
+            assert_eq!(ran, vec![1, 2, 3]);
+        }
+
+        #[test]
+        fn test_run_days_fail_fast() {
+            let (results, errors, ran) = run_stub_days(true);
+            assert_eq!(results, vec![1]);
+            assert_eq!(
+                errors,
+                vec!["02: could not parse the solution output: stub failure"]
+            );
+            assert_eq!(ran, vec![1, 2]);
+        }
+
+        #[test]
+        fn test_parse_heap_allocation() {
+            let parse = |line: &str| parse_heap_allocation(line).map(|bytes| bytes.to_string());
+
+            assert_eq!(
+                parse("Part 1: 42 (1.0ms) (1.5 KiB)"),
+                Some("1.5 KiB".into())
+            );
+            assert_eq!(parse("Part 1: 42 (1.0ms) (1.5KiB)"), Some("1.5 KiB".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms) (1.5 KB)"), Some("1.5 KB".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms) (1.5kb)"), Some("1.5 KB".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms) (2 MiB)"), Some("2 MiB".into()));
+            assert_eq!(
+                parse("Part 1: 42 (1.0ms) (2.25 MB)"),
+                Some("2250 KB".into())
+            );
+            assert_eq!(parse("Part 1: 42 (1.0ms) (1500 B)"), Some("1.5 KB".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms) (0 B)"), Some("0 B".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms) (0B)"), Some("0 B".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms) ( 10 KB )"), Some("10 KB".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms)(1.5 MiB)"), Some("1.5 MiB".into()));
+            assert_eq!(parse("Part 1: 42 (1.0ms) (1.0 KB/s)"), None);
+            assert_eq!(parse("Part 1: 42 (1.0ms) (1500)"), None);
+        }
 
         #[test]
         fn test_well_formed() {
@@ -234,10 +1869,38 @@ mod child_commands {
                     "".into(),
                 ],
                 day!(1),
-            );
+            )
+            .0;
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), ("74.13ns".into(), "10KB".into()));
-            assert_eq!(res.part_2.unwrap(), ("74.13ms".into(), "10KB".into()));
+            assert_eq!(res.part_1.unwrap(), ("74.13ns".into(), Bytes(10_000)));
+            assert_eq!(res.part_2.unwrap(), ("74.13ms".into(), Bytes(10_000)));
+            assert_eq!(res.samples, [Some(100_000), Some(99_999)]);
+        }
+
+        #[test]
+        fn test_without_heap_allocation() {
+            let (res, warnings) = parse_exec_bench(
+                &["Part 1: 6 (66.0ns @ 5 samples)".into(), "".into()],
+                day!(1),
+            );
+            assert!(warnings.is_empty());
+            assert_eq!(res.part_1.unwrap(), ("66.0ns".into(), Bytes(0)));
+            assert_eq!(res.samples, [Some(5), None]);
+        }
+
+        #[test]
+        fn test_single_part() {
+            let res = parse_exec_bench(
+                &[
+                    "Part 2: 10 (74.13ms @ 99999 samples) (10KB)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            )
+            .0;
+            assert_approx_eq!(res.total_nanos, 74130000_f64);
+            assert_eq!(res.part_1, None);
+            assert_eq!(res.part_2.unwrap(), ("74.13ms".into(), Bytes(10_000)));
         }
 
         #[test]
@@ -249,10 +1912,147 @@ mod child_commands {
                     "".into(),
                 ],
                 day!(1),
-            );
+            )
+            .0;
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), ("2s".into(), "10B".into()));
-            assert_eq!(res.part_2.unwrap(), ("100ms".into(), "10B".into()));
+            assert_eq!(res.part_1.unwrap(), ("2s".into(), Bytes(10)));
+            assert_eq!(res.part_2.unwrap(), ("100ms".into(), Bytes(10)));
+        }
+
+        #[test]
+        fn test_thousands_separators() {
+            let res = parse_exec_bench(
+                &[
+                    "Part 1: 0 (1,234.56ms @ 100,000 samples) (10KB)".into(),
+                    "Part 2: 10 (1_234ns @ 100_000 samples) (10KB)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            )
+            .0;
+            assert_approx_eq!(res.total_nanos, 1234561234_f64);
+            assert_eq!(res.part_1.unwrap(), ("1,234.56ms".into(), Bytes(10_000)));
+            assert_eq!(res.part_2.unwrap(), ("1_234ns".into(), Bytes(10_000)));
+        }
+
+        #[test]
+        fn test_stale_days() {
+            let table = [
+                empty_bench(day!(1)),
+                empty_bench(day!(2)),
+                empty_bench(day!(5)),
+            ];
+            let solutions = [day!(1), day!(5)];
+
+            assert_eq!(
+                stale_days(&table, |day| solutions.contains(&day)),
+                vec![day!(2)]
+            );
+            assert!(stale_days(&table, |_| true).is_empty());
+        }
+
+        #[test]
+        fn test_scientific_notation() {
+            let res = parse_exec_bench(
+                &[
+                    "Part 1: 0 (1.5e3ns @ 10 samples) (10KB)".into(),
+                    "Part 2: 10 (2E-3s @ 10 samples) (10KB)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            )
+            .0;
+            assert_approx_eq!(res.total_nanos, 2001500_f64);
+            assert_eq!(res.part_1.unwrap(), ("1.5e3ns".into(), Bytes(10_000)));
+            assert_eq!(res.part_2.unwrap(), ("2E-3s".into(), Bytes(10_000)));
+        }
+
+        #[test]
+        fn test_micro_sign_variants() {
+            let res = parse_exec_bench(
+                &[
+                    "Part 1: 0 (1.5\u{b5}s @ 10 samples) (10KB)".into(),
+                    "Part 2: 10 (2.5\u{3bc}s @ 10 samples) (10KB)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            )
+            .0;
+            assert_approx_eq!(res.total_nanos, 4000_f64);
+            assert_eq!(res.part_2.unwrap(), ("2.5\u{3bc}s".into(), Bytes(10_000)));
+        }
+
+        #[test]
+        fn test_zero_samples() {
+            let res = parse_exec_bench(
+                &["Part 1: 5 (0ns @ 0 samples) (0B)".into(), "".into()],
+                day!(1),
+            )
+            .0;
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.unwrap(), ("0ns".into(), Bytes(0)));
+            assert!(res.part_2.is_none());
+        }
+
+        #[test]
+        fn test_untimed_lines() {
+            let res = parse_exec_bench(
+                &["Part 1: 5 (1.2ms)".into(), "Part 2: 6 (3.4ms) (10B)".into()],
+                day!(1),
+            )
+            .0;
+            assert_eq!(res.part_1.unwrap(), ("".into(), Bytes(0)));
+            assert_eq!(res.part_2.unwrap(), ("".into(), Bytes(10)));
+        }
+
+        #[test]
+        fn test_malformed_lines() {
+            let (res, warnings) = parse_exec_bench(
+                &[
+                    "Part 1: 5 (10ns @ samples) (10B)".into(),
+                    "Part 2: 5 (-10ns @ 10 samples) (10B)".into(),
+                    "some debug output".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+            assert_eq!(
+                warnings,
+                vec![
+                    ParseWarning::Timing {
+                        line: "Part 1: 5 (10ns @ samples) (10B)".into()
+                    },
+                    ParseWarning::Timing {
+                        line: "Part 2: 5 (-10ns @ 10 samples) (10B)".into()
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_heap_warnings() {
+            let (res, warnings) = parse_exec_bench(
+                &[
+                    "Part 1: 5 (10ns @ 10 samples) (10 parsecs)".into(),
+                    "Part 2: 5 (10ns @ 10 samples) (10B)".into(),
+                ],
+                day!(1),
+            );
+            assert!(res.part_1.is_none());
+            assert_eq!(res.part_2.unwrap(), ("10ns".into(), Bytes(10)));
+            assert_eq!(
+                warnings,
+                vec![ParseWarning::Heap {
+                    line: "Part 1: 5 (10ns @ 10 samples) (10 parsecs)".into()
+                }]
+            );
+            assert_eq!(
+                warnings[0].to_string(),
+                "Could not parse heap allocation from line: Part 1: 5 (10ns @ 10 samples) (10 parsecs)"
+            );
         }
 
         #[test]
@@ -264,10 +2064,124 @@ mod child_commands {
                     "".into(),
                 ],
                 day!(1),
-            );
+            )
+            .0;
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::child_commands::empty_bench;
+    use super::{existing_days, format_summary, keep_other_part, namespaced_years, DayFilter};
+    use crate::template::paths;
+    use crate::template::readme_benchmarks::{Benchmark, Bytes};
+    use crate::{day, part};
+
+    #[test]
+    fn test_day_filter() {
+        let filter: DayFilter = "1,3,5-8".parse().unwrap();
+        let days: Vec<u8> = crate::all_days()
+            .filter(|day| filter.contains(*day))
+            .map(crate::Day::into_inner)
+            .collect();
+        assert_eq!(days, vec![1, 3, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_invalid_day_filter() {
+        assert!("1,x".parse::<DayFilter>().is_err());
+        assert!("0-3".parse::<DayFilter>().is_err());
+        assert!("8-5".parse::<DayFilter>().is_err());
+        assert!("26".parse::<DayFilter>().is_err());
+    }
+
+    #[test]
+    fn test_existing_days() {
+        let dir = std::env::temp_dir().join(format!("aoc-bins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for file in [
+            "12.rs", "03.rs", "1.rs", "26.rs", "notes.rs", "04.txt", "day05.rs",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        std::fs::write(dir.join("y2022_07.rs"), "").unwrap();
+
+        let days = existing_days(&dir, paths::DEFAULT_BIN_NAME, None);
+        let custom_days = existing_days(&dir, "day{day_padded}.rs", None);
+        let year_days = existing_days(&dir, "y{year}_{day_padded}.rs", Some(2022));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(days.unwrap(), vec![day!(3), day!(12)]);
+        assert_eq!(custom_days.unwrap(), vec![day!(5)]);
+        assert_eq!(year_days.unwrap(), vec![day!(7)]);
+
+        assert!(existing_days(&dir, paths::DEFAULT_BIN_NAME, None).is_err());
+    }
+
+    #[test]
+    fn test_namespaced_years() {
+        let dir = std::env::temp_dir().join(format!("aoc-years-{}", std::process::id()));
+        for sub in ["2023", "2022", "inputs", "123", "20x4"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
         }
+        std::fs::write(dir.join("2021"), "").unwrap();
+
+        let years = namespaced_years(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(years.unwrap(), vec![2022, 2023]);
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let benchmarks = [
+            Benchmark {
+                total_nanos: 1_000_000_f64,
+                ..empty_bench(day!(1))
+            },
+            Benchmark {
+                total_nanos: 234_560_000_f64,
+                ..empty_bench(day!(2))
+            },
+            Benchmark {
+                timed_out: true,
+                total_nanos: 5_000_000_000_f64,
+                ..empty_bench(day!(3))
+            },
+        ];
+
+        assert_eq!(
+            format_summary(&benchmarks, 1),
+            "SUMMARY solved=2 errored=1 timeout=1 total_ms=235.56"
+        );
+        assert_eq!(
+            format_summary(&[], 0),
+            "SUMMARY solved=0 errored=0 timeout=0 total_ms=0.00"
+        );
+    }
+
+    #[test]
+    fn test_keep_other_part() {
+        let mut new = Benchmark {
+            part_1: Some(("1ms".into(), Bytes(10))),
+            total_nanos: 1_000_000_f64,
+            samples: [Some(100), None],
+            ..empty_bench(day!(1))
+        };
+        let old = Benchmark {
+            part_1: Some(("5ms".into(), Bytes(50))),
+            part_2: Some(("2ms".into(), Bytes(20))),
+            samples: [Some(10), Some(500)],
+            ..empty_bench(day!(1))
+        };
+
+        keep_other_part(&mut new, old, part!(1));
+        assert_eq!(new.part_1, Some(("1ms".into(), Bytes(10))));
+        assert_eq!(new.part_2, Some(("2ms".into(), Bytes(20))));
+        assert_eq!(new.samples, [Some(100), Some(500)]);
+        assert!((new.total_nanos - 3_000_000_f64).abs() < 1e-6);
     }
 }
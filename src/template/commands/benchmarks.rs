@@ -0,0 +1,80 @@
+use std::io;
+use std::process;
+use std::time::Duration;
+
+use crate::template::benchmark_history::{self, format_timestamp, summarize, Error};
+use crate::template::logging::{error, info};
+use crate::template::paths;
+use crate::template::theme::theme;
+use crate::Day;
+
+pub fn handle(day: Option<Day>, summary: bool) {
+    let records = match benchmark_history::read() {
+        Ok(records) => records,
+        Err(Error::IO(e)) if e.kind() == io::ErrorKind::NotFound => {
+            info!(
+                "No benchmark history found at \"{}\". Run `cargo all --release --time --store` to record one.",
+                paths::history_path()
+            );
+            return;
+        }
+        Err(e) => {
+            error!("Failed to read the benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+
+    let theme = theme();
+
+    if summary {
+        println!(
+            "{}| Day | Runs | Best | Worst | Median |{}",
+            theme.label, theme.reset
+        );
+        println!("| :---: | :---: | :---: | :---: | :---: |");
+        for summary in summarize(&records) {
+            println!(
+                "| {} | {} | {} | {} | {} |",
+                summary.day,
+                summary.runs,
+                format_nanos(summary.best_nanos),
+                format_nanos(summary.worst_nanos),
+                format_nanos(summary.median_nanos)
+            );
+        }
+        return;
+    }
+
+    let Some(day) = day else {
+        error!("Specify a day, e.g. `cargo benchmarks 1`, or pass `--summary`.");
+        process::exit(1);
+    };
+
+    let records: Vec<_> = records.iter().filter(|record| record.day == day).collect();
+    if records.is_empty() {
+        info!("No benchmark of day {day} recorded yet.");
+        return;
+    }
+
+    println!(
+        "{}| Date | Part 1 | Part 2 | Total |{}",
+        theme.label, theme.reset
+    );
+    println!("| :---: | :---: | :---: | :---: |");
+    for record in records {
+        println!(
+            "| {} | {} | {} | {} |",
+            format_timestamp(record.timestamp),
+            record.part_1.as_deref().unwrap_or("-"),
+            record.part_2.as_deref().unwrap_or("-"),
+            format_nanos(record.total_nanos)
+        );
+    }
+}
+
+/// Formats a total time with the most appropriate unit, e.g. `1.20µs`.
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.2?}")
+}
@@ -0,0 +1,185 @@
+use std::path::Path;
+use std::process::{self, Command};
+
+use crate::template::logging::info;
+use crate::template::paths;
+use crate::template::theme::theme;
+use crate::{all_days, Day};
+
+/// The outcome of the example tests of a day.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ExampleResult {
+    passed: usize,
+    failed: usize,
+    failures: Vec<String>,
+}
+
+impl ExampleResult {
+    fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+pub fn handle() {
+    let mut results: Vec<(Day, Option<ExampleResult>)> = vec![];
+
+    for day in all_days().filter(|day| Path::new(&paths::bin_path(*day)).exists()) {
+        info!("Testing day {day}...");
+        results.push((day, run_tests(day)));
+    }
+
+    if results.is_empty() {
+        info!("No scaffolded days to test.");
+        return;
+    }
+
+    let theme = theme();
+    println!();
+    println!(
+        "{}| Day | Passed | Failed | Status |{}",
+        theme.label, theme.reset
+    );
+    println!("| :---: | :---: | :---: | :---: |");
+
+    for (day, result) in &results {
+        match result {
+            Some(result) => {
+                let status = if result.is_success() {
+                    format!("{}ok{}", theme.success, theme.reset)
+                } else {
+                    format!("{}FAILED{}", theme.error, theme.reset)
+                };
+                println!(
+                    "| {day} | {} | {} | {status} |",
+                    result.passed, result.failed
+                );
+            }
+            None => println!("| {day} | - | - | {}error{} |", theme.error, theme.reset),
+        }
+    }
+
+    let failing: Vec<_> = results
+        .iter()
+        .filter(|(_, result)| !result.as_ref().is_some_and(ExampleResult::is_success))
+        .collect();
+
+    if failing.is_empty() {
+        return;
+    }
+
+    println!();
+    for (day, result) in &failing {
+        match result {
+            Some(result) => {
+                for name in &result.failures {
+                    println!("Day {day}: {name} failed");
+                }
+            }
+            None => println!("Day {day}: tests could not be run"),
+        }
+    }
+
+    process::exit(1);
+}
+
+/// Runs the example tests of a day, returns [`None`] if no test results could be parsed
+/// (e.g. because the solution does not compile).
+fn run_tests(day: Day) -> Option<ExampleResult> {
+    let output = Command::new("cargo")
+        .args(["test", "--bin", &paths::bin_name(day)])
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let result = parse_test_output(stdout.lines());
+
+    if result.is_none() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    result
+}
+
+/// Parses the output of `cargo test`, looking for the `test result:` summary and the names of the
+/// failing tests.
+fn parse_test_output<'a>(lines: impl Iterator<Item = &'a str>) -> Option<ExampleResult> {
+    let mut result = ExampleResult::default();
+    let mut has_summary = false;
+
+    for line in lines {
+        if let Some(name) = line
+            .strip_prefix("test ")
+            .and_then(|l| l.strip_suffix(" ... FAILED"))
+        {
+            result.failures.push(name.to_string());
+        }
+
+        if let Some(summary) = line.strip_prefix("test result: ") {
+            has_summary = true;
+            result.passed += parse_count(summary, " passed")?;
+            result.failed += parse_count(summary, " failed")?;
+        }
+    }
+
+    has_summary.then_some(result)
+}
+
+fn parse_count(summary: &str, label: &str) -> Option<usize> {
+    summary
+        .split(';')
+        .find_map(|part| part.trim().strip_suffix(label))?
+        .rsplit(' ')
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_test_output, ExampleResult};
+
+    #[test]
+    fn parses_passing_tests() {
+        let output = [
+            "",
+            "running 2 tests",
+            "..",
+            "test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s",
+        ];
+        assert_eq!(
+            parse_test_output(output.into_iter()),
+            Some(ExampleResult {
+                passed: 2,
+                failed: 0,
+                failures: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn parses_failing_tests() {
+        let output = [
+            "running 2 tests",
+            "test tests::test_part_one ... ok",
+            "test tests::test_part_two ... FAILED",
+            "",
+            "failures:",
+            "test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s",
+        ];
+        assert_eq!(
+            parse_test_output(output.into_iter()),
+            Some(ExampleResult {
+                passed: 1,
+                failed: 1,
+                failures: vec!["tests::test_part_two".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn missing_summary() {
+        let output = ["error[E0308]: mismatched types"];
+        assert_eq!(parse_test_output(output.into_iter()), None);
+    }
+}
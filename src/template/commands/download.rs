@@ -1,15 +1,42 @@
 use crate::template::aoc_cli;
+use crate::template::logging::error;
+#[cfg(feature = "checksum")]
+use crate::template::logging::{info, warning};
+#[cfg(feature = "checksum")]
+use crate::template::{checksum, paths};
 use crate::Day;
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, retries: u32) {
     if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        error!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    match aoc_cli::download(day, retries) {
+        #[cfg(feature = "checksum")]
+        Ok(_) => match checksum::record(day) {
+            Ok(()) => info!(
+                "🎄 Recorded the checksum of the input to \"{}\".",
+                paths::checksum_path(day)
+            ),
+            Err(e) => warning!("Failed to record the checksum of the input: {e}"),
+        },
+        #[cfg(not(feature = "checksum"))]
+        Ok(_) => {}
+        Err(e @ aoc_cli::AocCommandError::InvalidSession(_)) => {
+            error!("failed to download input: {e} Refresh your \".adventofcode.session\" file.");
+            process::exit(1);
+        }
+        Err(aoc_cli::AocCommandError::BadExitStatus(_)) => {
+            error!(
+                "failed to download input: the server is temporarily unavailable, try again later."
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            error!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        }
     };
 }
@@ -1,5 +1,12 @@
 pub mod all;
+pub mod benchmarks;
+pub mod check_examples;
 pub mod download;
+pub mod open;
+pub mod paste;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
+pub mod time;
+pub mod verify;
@@ -0,0 +1,40 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::aoc_cli;
+use crate::template::logging::{error, info};
+use crate::Day;
+
+/// The command opening an URL in the default browser of the platform.
+fn open_command(url: &str) -> Command {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+
+    cmd.arg(url).stdout(Stdio::null()).stderr(Stdio::null());
+    cmd
+}
+
+#[must_use]
+pub fn puzzle_url(year: u16, day: Day) -> String {
+    format!("https://adventofcode.com/{year}/day/{}", day.into_inner())
+}
+
+pub fn handle(day: Day) {
+    let Some(year) = aoc_cli::get_year() else {
+        error!("Could not determine the puzzle year, set the \"AOC_YEAR\" environment variable.");
+        process::exit(1);
+    };
+
+    let url = puzzle_url(year, day);
+
+    match open_command(&url).status() {
+        Ok(status) if status.success() => info!("🎄 Opened {url}"),
+        _ => info!("Could not open a browser, the puzzle is available at: {url}"),
+    }
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use crate::template::logging::{error, info};
+use crate::template::paths;
+use crate::Day;
+
+/// Removes a single trailing line break of the pasted input, copying the input from the browser
+/// usually picks up an extra one.
+fn trim_trailing_newline(input: &str) -> &str {
+    input
+        .strip_suffix("\r\n")
+        .or_else(|| input.strip_suffix('\n'))
+        .unwrap_or(input)
+}
+
+#[cfg(feature = "clipboard")]
+fn read_clipboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn read_clipboard() -> Result<String, String> {
+    Err("the clipboard is not supported by this build, enable the `clipboard` feature".into())
+}
+
+pub fn handle(day: Day, force: bool) {
+    let input_path = paths::input_path(day);
+
+    let has_input = fs::metadata(&input_path).is_ok_and(|metadata| metadata.len() > 0);
+    if has_input && !force {
+        error!("The input file \"{input_path}\" is not empty, pass `--force` to overwrite it.");
+        process::exit(1);
+    }
+
+    let input = match read_clipboard() {
+        Ok(input) => input,
+        Err(e) => {
+            error!("Failed to read the clipboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    if input.trim().is_empty() {
+        error!("The clipboard is empty, copy your puzzle input first.");
+        process::exit(1);
+    }
+
+    if let Some(dir) = Path::new(&input_path).parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            error!("Failed to create the inputs directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    match fs::write(&input_path, trim_trailing_newline(&input)) {
+        Ok(()) => info!("🎄 Pasted the input of day {day} to \"{input_path}\"."),
+        Err(e) => {
+            error!("Failed to write the input file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::trim_trailing_newline;
+
+    #[test]
+    fn trims_a_single_newline() {
+        assert_eq!(trim_trailing_newline("1 2\n3 4\n"), "1 2\n3 4");
+        assert_eq!(trim_trailing_newline("1 2\r\n3 4\r\n"), "1 2\r\n3 4");
+        assert_eq!(trim_trailing_newline("1 2\n\n"), "1 2\n");
+        assert_eq!(trim_trailing_newline("1 2"), "1 2");
+    }
+}
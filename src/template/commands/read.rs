@@ -1,16 +1,389 @@
-use std::process;
+use std::collections::HashSet;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
+use crate::template::logging::{error, info};
+use crate::template::theme::theme;
+use crate::template::{aoc_cli, paths, read_input_checked};
 use crate::Day;
 
-pub fn handle(day: Day) {
+/// Statistics about the contents of an input, printed by `read --stats`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct InputStats {
+    lines: usize,
+    max_line_length: usize,
+    bytes: usize,
+    distinct_chars: usize,
+}
+
+impl InputStats {
+    fn analyze(input: &str) -> Self {
+        Self {
+            lines: input.lines().count(),
+            max_line_length: input
+                .lines()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or_default(),
+            bytes: input.len(),
+            distinct_chars: input
+                .chars()
+                .filter(|c| *c != '\n' && *c != '\r')
+                .collect::<HashSet<_>>()
+                .len(),
+        }
+    }
+}
+
+/// The number of unchanged lines displayed around each change of a diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// Common prefixes and suffixes aside, inputs whose number of line pairs exceeds this are not
+/// aligned line by line, their remaining lines are displayed as replaced as a whole.
+const MAX_DIFF_CELLS: usize = 25_000_000;
+
+/// A line of the diff between two inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Computes a line diff turning `old` into `new`, aligning the lines with a longest common
+/// subsequence.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // truncated or mis-pasted inputs mostly share their beginning or their end.
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut diff: Vec<DiffLine> = old[..prefix].iter().map(|l| DiffLine::Same(l)).collect();
+
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_DIFF_CELLS {
+        diff.extend(old_middle.iter().map(|l| DiffLine::Removed(l)));
+        diff.extend(new_middle.iter().map(|l| DiffLine::Added(l)));
+    } else {
+        // lengths[i][j] is the length of the common subsequence of old[i..] and new[j..].
+        let (n, m) = (old_middle.len(), new_middle.len());
+        let mut lengths = vec![vec![0_u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lengths[i][j] = if old_middle[i] == new_middle[j] {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old_middle[i] == new_middle[j] {
+                diff.push(DiffLine::Same(old_middle[i]));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lengths[i + 1][j] >= lengths[i][j + 1]) {
+                diff.push(DiffLine::Removed(old_middle[i]));
+                i += 1;
+            } else {
+                diff.push(DiffLine::Added(new_middle[j]));
+                j += 1;
+            }
+        }
+    }
+
+    diff.extend(old[old.len() - suffix..].iter().map(|l| DiffLine::Same(l)));
+    diff
+}
+
+/// Formats the diff between the inputs `old` and `new` as a unified diff, with the usual
+/// `---`/`+++` headers and `@@ -start,count +start,count @@` hunk headers. A missing line break at
+/// the end of an input is flagged with `\ No newline at end of file`. Returns no line for equal
+/// inputs.
+fn unified_diff(old_name: &str, new_name: &str, old: &str, new: &str) -> Vec<String> {
+    // the line breaks are part of the lines, so that a missing trailing one is a change.
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let diff = diff_lines(&old_lines, &new_lines);
+
+    let changes: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+    if changes.is_empty() {
+        return vec![];
+    }
+
+    // groups the changes that are close enough to share their context.
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for &index in &changes {
+        let start = index.saturating_sub(DIFF_CONTEXT);
+        let end = (index + DIFF_CONTEXT + 1).min(diff.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut lines = vec![format!("--- {old_name}"), format!("+++ {new_name}")];
+    // the line numbers of both inputs at the start of each diff line.
+    let mut positions = Vec::with_capacity(diff.len());
+    let (mut old_line, mut new_line) = (1, 1);
+    for line in &diff {
+        positions.push((old_line, new_line));
+        match line {
+            DiffLine::Same(_) => (old_line, new_line) = (old_line + 1, new_line + 1),
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+
+    // an empty hunk side starts at the line before it, as in `diff -u`.
+    let hunk_start = |line: usize, count: usize| if count == 0 { line - 1 } else { line };
+
+    for (start, end) in hunks {
+        let hunk = &diff[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Removed(_)))
+            .count();
+        let (old_start, new_start) = positions[start];
+        lines.push(format!(
+            "@@ -{},{old_count} +{},{new_count} @@",
+            hunk_start(old_start, old_count),
+            hunk_start(new_start, new_count),
+        ));
+
+        for line in hunk {
+            let (prefix, text) = match line {
+                DiffLine::Same(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            match text.strip_suffix('\n') {
+                Some(text) => lines.push(format!("{prefix}{text}")),
+                None => {
+                    lines.push(format!("{prefix}{text}"));
+                    lines.push("\\ No newline at end of file".into());
+                }
+            }
+        }
+    }
+
+    lines
+}
+
+/// Returns the number of the first line that differs between the inputs, starting at 1.
+fn first_difference(old: &str, new: &str) -> Option<usize> {
+    let mut old_lines = old.lines();
+    let mut new_lines = new.lines();
+    let mut line = 1;
+    loop {
+        match (old_lines.next(), new_lines.next()) {
+            (None, None) => return None,
+            (a, b) if a != b => return Some(line),
+            _ => line += 1,
+        }
+    }
+}
+
+pub fn handle(day: Day, stats: bool, compare_input: Option<String>) {
+    if let Some(other) = compare_input {
+        compare(day, &other);
+        return;
+    }
+
+    if stats {
+        print_stats(day);
+        return;
+    }
+
     if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        error!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+        error!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+/// Prints a unified diff between the input of the day and the file `other`.
+fn compare(day: Day, other: &str) {
+    let input = match read_input_checked(day) {
+        Ok(input) => input,
+        Err(e) => {
+            error!("Could not read the input of day {day}: {e}");
+            process::exit(1);
+        }
+    };
+    let other_input = match fs::read_to_string(other) {
+        Ok(other_input) => other_input,
+        Err(e) => {
+            error!("Could not read \"{other}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = paths::input_path(day);
+    let diff = unified_diff(&input_path, other, &input, &other_input);
+    if diff.is_empty() {
+        info!("The input of day {day} and \"{other}\" are identical.");
+        return;
+    }
+
+    let theme = theme();
+    let (input_lines, other_lines) = (input.lines().count(), other_input.lines().count());
+    match first_difference(&input, &other_input) {
+        Some(line) => println!(
+            "{}First difference at line {line}.{}",
+            theme.emphasis, theme.reset
+        ),
+        None => println!(
+            "{}The inputs only differ by their trailing line break.{}",
+            theme.emphasis, theme.reset
+        ),
+    }
+    if input_lines != other_lines {
+        println!(
+            "{}\"{input_path}\" has {input_lines} lines, \"{other}\" has {other_lines} lines.{}",
+            theme.emphasis, theme.reset
+        );
+    }
+    println!();
+
+    for line in diff {
+        let color = match line.chars().next() {
+            Some('-') if !line.starts_with("---") => &theme.error,
+            Some('+') if !line.starts_with("+++") => &theme.success,
+            Some('@') => &theme.label,
+            _ => "",
+        };
+        let reset = if color.is_empty() { "" } else { &theme.reset };
+        println!("{color}{line}{reset}");
+    }
+}
+
+fn print_stats(day: Day) {
+    let input = match read_input_checked(day) {
+        Ok(input) => input,
+        Err(e) => {
+            error!("Could not read the input of day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let stats = InputStats::analyze(&input);
+    let theme = theme();
+    for (label, value) in [
+        ("Lines", stats.lines),
+        ("Max line length", stats.max_line_length),
+        ("Bytes", stats.bytes),
+        ("Distinct characters", stats.distinct_chars),
+    ] {
+        println!("{}{label}:{} {value}", theme.label, theme.reset);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{first_difference, unified_diff, InputStats};
+
+    #[test]
+    fn analyzes_input() {
+        assert_eq!(
+            InputStats::analyze("1 2\r\n30 40\r\n"),
+            InputStats {
+                lines: 2,
+                max_line_length: 5,
+                bytes: 12,
+                distinct_chars: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn analyzes_empty_input() {
+        assert_eq!(InputStats::analyze(""), InputStats::default());
+    }
+
+    #[test]
+    fn diffs_identical_inputs() {
+        assert!(unified_diff("a", "b", "1\n2\n", "1\n2\n").is_empty());
+        assert_eq!(first_difference("1\n2\n", "1\n2\n"), None);
+    }
+
+    #[test]
+    fn diffs_changed_lines() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n";
+        assert_eq!(
+            unified_diff("a", "b", old, new),
+            [
+                "--- a",
+                "+++ b",
+                "@@ -2,7 +2,7 @@",
+                " 2",
+                " 3",
+                " 4",
+                "-5",
+                "+five",
+                " 6",
+                " 7",
+                " 8",
+            ]
+        );
+        assert_eq!(first_difference(old, new), Some(5));
+    }
+
+    #[test]
+    fn diffs_truncated_inputs() {
+        let old = "1\n2\n3\n";
+        let new = "1\n2";
+        assert_eq!(
+            unified_diff("a", "b", old, new),
+            [
+                "--- a",
+                "+++ b",
+                "@@ -1,3 +1,2 @@",
+                " 1",
+                "-2",
+                "-3",
+                "+2",
+                "\\ No newline at end of file",
+            ]
+        );
+        assert_eq!(first_difference(old, new), Some(3));
+    }
+
+    #[test]
+    fn diffs_trailing_newlines() {
+        assert_eq!(
+            unified_diff("a", "b", "1\n2\n", "1\n2"),
+            [
+                "--- a",
+                "+++ b",
+                "@@ -1,2 +1,2 @@",
+                " 1",
+                "-2",
+                "+2",
+                "\\ No newline at end of file",
+            ]
+        );
+        assert_eq!(first_difference("1\n2\n", "1\n2"), None);
+    }
+}
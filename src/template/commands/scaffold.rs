@@ -1,13 +1,24 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fmt::Display,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::{self, Command, ExitStatus},
 };
 
-use crate::Day;
+use crate::template::aoc_cli;
+use crate::template::commands::open::puzzle_url;
+use crate::template::logging::{self, debug, error, info, warning};
+use crate::template::paths;
+use crate::template::readme_benchmarks::MARKER;
+use crate::{all_days, Day, Part};
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
+#[global_allocator]
+static ALLOC: advent_of_code::template::alloc::CountingAllocator =
+    advent_of_code::template::alloc::CountingAllocator;
+
 pub fn part_one(input: &str) -> Option<u32> {
     None
 }
@@ -20,88 +31,511 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    advent_of_code::part!();
+    use advent_of_code::prelude::*;
+    part!();
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_example(DAY, PART_ONE));
-        assert_eq!(result, None);
+    fn test_parts() {
+        for part in Part::ALL {
+            let example = read_example(DAY, part);
+            let result = match part {
+                PART_ONE => part_one(&example).map(|answer| answer.to_string()),
+                _ => part_two(&example).map(|answer| answer.to_string()),
+            };
+            assert_eq!(result, read_example_answer(DAY, part), "part {part}");
+        }
     }
+}
+"#;
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_example(DAY, PART_TWO));
-        assert_eq!(result, None);
+/// The placeholder replaced with the day number in the module templates.
+const DAY_NUMBER: &str = "DAY_NUMBER";
+
+/// An error which can be returned when validating a custom module template.
+#[derive(Debug, PartialEq, Eq)]
+enum TemplateError {
+    /// The template does not invoke the `solution!` macro.
+    MissingSolutionMacro,
+    /// The `solution!` macro is not invoked with the `DAY_NUMBER` placeholder.
+    MissingDayNumber,
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::MissingSolutionMacro => write!(
+                f,
+                "the template does not invoke `advent_of_code::solution!({DAY_NUMBER});`"
+            ),
+            TemplateError::MissingDayNumber => write!(
+                f,
+                "the `solution!` macro of the template is not invoked with `{DAY_NUMBER}`"
+            ),
+        }
     }
 }
-"#;
+
+impl std::error::Error for TemplateError {}
+
+/// Checks that a module template invokes `solution!(DAY_NUMBER)`, without which the created
+/// binary would not run the solution of its day. Whitespace around the argument is allowed.
+fn validate_template(template: &str) -> Result<(), TemplateError> {
+    let mut invocations = template.match_indices("solution!").peekable();
+    if invocations.peek().is_none() {
+        return Err(TemplateError::MissingSolutionMacro);
+    }
+
+    let takes_day_number = invocations.any(|(index, invocation)| {
+        template[index + invocation.len()..]
+            .trim_start()
+            .strip_prefix('(')
+            .is_some_and(|argument| argument.trim_start().starts_with(DAY_NUMBER))
+    });
+
+    if takes_day_number {
+        Ok(())
+    } else {
+        Err(TemplateError::MissingDayNumber)
+    }
+}
+
+/// Reads the module template, the custom template at `path` if any or the default one.
+fn read_template(path: Option<&str>) -> Result<String, String> {
+    let Some(path) = path else {
+        return Ok(MODULE_TEMPLATE.into());
+    };
+
+    debug!("Reading the module template from \"{path}\"");
+    let template = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read the template \"{path}\": {e}"))?;
+    validate_template(&template).map_err(|e| format!("Invalid template \"{path}\": {e}"))?;
+    Ok(template)
+}
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// The `.gitignore` entry matching the inputs directory.
+fn inputs_ignore_entry() -> String {
+    format!("{}/", paths::inputs_dir().trim_start_matches("./"))
+}
+
+/// Returns the `.gitignore` content with the `entry` directory ignored,
+/// or [`None`] if it is already ignored.
+fn ignore_inputs(gitignore: &str, entry: &str) -> Option<String> {
+    let trimmed_entry = entry.trim_end_matches('/');
+    let is_ignored = gitignore
+        .lines()
+        .any(|line| line.trim().trim_start_matches('/').trim_end_matches('/') == trimmed_entry);
+
+    if is_ignored {
+        return None;
+    }
+
+    let mut content = gitignore.to_string();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(entry);
+    content.push('\n');
+
+    Some(content)
+}
+
+/// Makes sure the puzzle inputs are never committed by listing them in the `.gitignore` file.
+fn ensure_inputs_ignored() -> Result<bool, io::Error> {
+    let path = ".gitignore";
+    let gitignore = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    match ignore_inputs(&gitignore, &inputs_ignore_entry()) {
+        Some(content) => fs::write(path, content).map(|()| true),
+        None => Ok(false),
+    }
+}
+
+/// Returns the first day that does not have a solution binary yet.
+fn next_unsolved_day() -> Option<Day> {
+    all_days().find(|day| !Path::new(&paths::bin_path(*day)).exists())
+}
+
+/// Formats a file with `rustfmt`, returning [`None`] if `rustfmt` is not installed.
+fn format_file(path: &str) -> Option<io::Result<ExitStatus>> {
+    let args = ["--edition", "2021", path];
+    logging::trace_command("rustfmt", &args);
+    match Command::new("rustfmt").args(args).status() {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        status => Some(status),
+    }
+}
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// The cargo configuration holding the `AOC_YEAR` environment variable.
+const CARGO_CONFIG_PATH: &str = ".cargo/config.toml";
+
+/// Returns the cargo configuration with `AOC_YEAR` set to `year`,
+/// or [`None`] if it already sets a year, which is never overwritten.
+fn with_year(config: &str, year: u16) -> Option<String> {
+    if config
+        .lines()
+        .any(|line| line.trim_start().starts_with("AOC_YEAR"))
+    {
+        return None;
+    }
+
+    let entry = format!("AOC_YEAR = \"{year}\"");
+    let mut lines: Vec<&str> = config.lines().collect();
+    match lines.iter().position(|line| line.trim() == "[env]") {
+        Some(index) => lines.insert(index + 1, &entry),
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push("");
+            }
+            lines.extend(["[env]", &entry]);
+        }
+    }
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Returns the README with an empty benchmarking table appended,
+/// or [`None`] if it already contains the table markers.
+fn with_table_markers(readme: &str) -> Option<String> {
+    if readme.contains(MARKER) {
+        return None;
+    }
+
+    let mut content = readme.to_string();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str(&format!("{MARKER}\n{MARKER}\n"));
+
+    Some(content)
+}
+
+/// Reads a file, an empty string being returned for missing files.
+fn read_or_empty(path: &str) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        result => result,
+    }
+}
+
+/// Applies `update` to the content of a file, writing it only if `update` changed something.
+/// Returns `true` if the file was written.
+fn update_file(path: &str, update: impl FnOnce(&str) -> Option<String>) -> io::Result<bool> {
+    match update(&read_or_empty(path)?) {
+        Some(content) => {
+            if let Some(dir) = Path::new(path)
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+            {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, content).map(|()| true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Bootstraps the layout of a new repository for the puzzles of `year`: the solution, input and
+/// example directories, the README benchmarking table, the `AOC_YEAR` of the cargo configuration
+/// and the `.gitignore` entry of the inputs.
+///
+/// Running it again is harmless, existing files and settings are never overwritten.
+pub fn init(year: u16) {
+    if year < FIRST_YEAR {
+        error!("Advent of code started in {FIRST_YEAR}, there are no puzzles in {year}.");
+        process::exit(1);
+    }
+
+    for dir in [paths::bin_dir(), paths::inputs_dir(), paths::examples_dir()] {
+        let exists = Path::new(&dir).is_dir();
+        match fs::create_dir_all(&dir) {
+            Ok(()) if !exists => info!("Created directory \"{dir}\""),
+            Ok(()) => {}
+            Err(e) => {
+                error!("Failed to create directory \"{dir}\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    match update_file("README.md", with_table_markers) {
+        Ok(true) => info!("Added the benchmarking table markers to \"README.md\""),
+        Ok(false) => {}
+        Err(e) => {
+            error!("Failed to update \"README.md\": {e}");
+            process::exit(1);
+        }
+    }
+
+    match update_file(CARGO_CONFIG_PATH, |config| with_year(config, year)) {
+        Ok(true) => info!("Set AOC_YEAR to {year} in \"{CARGO_CONFIG_PATH}\""),
+        Ok(false) => {
+            if aoc_cli::get_year().is_some_and(|current| current != year) {
+                warning!(
+                    "\"{CARGO_CONFIG_PATH}\" already sets another AOC_YEAR, it was left untouched."
+                );
+            }
+        }
+        Err(e) => {
+            error!("Failed to update \"{CARGO_CONFIG_PATH}\": {e}");
+            process::exit(1);
+        }
+    }
+
+    match ensure_inputs_ignored() {
+        Ok(true) => {
+            info!("Added \"{}\" to \".gitignore\"", inputs_ignore_entry());
+        }
+        Ok(false) => {}
+        Err(e) => {
+            error!("Failed to update \".gitignore\": {e}");
+        }
+    }
+
+    println!("---");
+    info!("🎄 Ready for {year}! Type `cargo scaffold 1` to scaffold the first day.");
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path_part_one = format!("data/examples/{day}-1.txt");
-    let example_path_part_two = format!("data/examples/{day}-2.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(day: Option<Day>, format: bool, shared_example: bool, template: Option<String>) {
+    // the template is validated before any file is created.
+    let template = match read_template(template.as_deref()) {
+        Ok(template) => template,
+        Err(e) => {
+            error!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let day = match day {
+        Some(day) => day,
+        None => match next_unsolved_day() {
+            Some(day) => {
+                info!("No day specified, scaffolding the next unsolved day: {day}");
+                day
+            }
+            None => {
+                info!("🎄 All 25 days are already scaffolded, merry Christmas! 🎄");
+                return;
+            }
+        },
+    };
+    let input_path = paths::input_path(day);
+    let module_path = paths::bin_path(day);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            error!("Failed to create module file: {e}");
             process::exit(1);
         }
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
+        template
+            .replace(DAY_NUMBER, &day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            info!("Created module file \"{}\"", &module_path);
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            error!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
 
+    if format {
+        match format_file(&module_path) {
+            Some(Ok(status)) if status.success() => {
+                info!("Formatted module file \"{}\"", &module_path);
+            }
+            Some(Ok(_)) => warning!("rustfmt could not format \"{}\"", &module_path),
+            Some(Err(e)) => warning!("Failed to run rustfmt: {e}"),
+            None => warning!("rustfmt not found, skipping formatting. Install it with \"rustup component add rustfmt\"."),
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            info!("Created empty input file \"{}\"", &input_path);
         }
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            error!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path_part_one) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path_part_one);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    let example_paths = if shared_example {
+        vec![paths::shared_example_path(day)]
+    } else {
+        Part::ALL
+            .into_iter()
+            .map(|part| paths::example_path(day, part))
+            .collect()
+    };
+
+    for example_path in example_paths {
+        match create_file(&example_path) {
+            Ok(_) => {
+                info!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => {
+                error!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path_part_two) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path_part_two);
+    match ensure_inputs_ignored() {
+        Ok(true) => {
+            info!("Added \"{}\" to \".gitignore\"", inputs_ignore_entry());
         }
+        Ok(false) => {}
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+            error!("Failed to update \".gitignore\": {e}");
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    for step in next_steps(day, aoc_cli::get_year(), aoc_cli::has_session()) {
+        info!("{step}");
+    }
+}
+
+/// The steps suggested once a day is scaffolded: solving it, reading its puzzle if the year is
+/// known and downloading its input if a session cookie is configured.
+fn next_steps(day: Day, year: Option<u16>, session: bool) -> Vec<String> {
+    let mut steps = vec![format!("🎄 Type `cargo solve {day}` to run your solution.")];
+    if let Some(year) = year {
+        steps.push(format!("🎄 Read the puzzle at {}", puzzle_url(year, day)));
+    }
+    if session {
+        steps.push(format!(
+            "🎄 Type `cargo download {day}` to download your input."
+        ));
+    }
+    steps
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        ignore_inputs, next_steps, validate_template, with_table_markers, with_year, TemplateError,
+        MARKER, MODULE_TEMPLATE,
+    };
+    use crate::day;
+
+    const ENTRY: &str = "data/inputs/";
+
+    #[test]
+    fn adds_missing_entry() {
+        assert_eq!(ignore_inputs("", ENTRY), Some("data/inputs/\n".into()));
+        assert_eq!(
+            ignore_inputs("target/\nCargo.lock", ENTRY),
+            Some("target/\nCargo.lock\ndata/inputs/\n".into())
+        );
+    }
+
+    #[test]
+    fn keeps_existing_entry() {
+        assert_eq!(ignore_inputs("target/\ndata/inputs/\n", ENTRY), None);
+        assert_eq!(ignore_inputs("/data/inputs\ntarget/\n", ENTRY), None);
+    }
+
+    #[test]
+    fn is_idempotent() {
+        let once = ignore_inputs("target/\n", ENTRY).unwrap();
+        assert_eq!(ignore_inputs(&once, ENTRY), None);
+    }
+
+    #[test]
+    fn sets_missing_year() {
+        assert_eq!(
+            with_year("", 2024),
+            Some("[env]\nAOC_YEAR = \"2024\"\n".into())
+        );
+        assert_eq!(
+            with_year("[alias]\nsolve = \"run\"", 2024),
+            Some("[alias]\nsolve = \"run\"\n\n[env]\nAOC_YEAR = \"2024\"\n".into())
+        );
+        assert_eq!(
+            with_year("[env]\nAOC_DATA_DIR = \"data\"\n", 2024),
+            Some("[env]\nAOC_YEAR = \"2024\"\nAOC_DATA_DIR = \"data\"\n".into())
+        );
+    }
+
+    #[test]
+    fn keeps_existing_year() {
+        assert_eq!(with_year("[env]\nAOC_YEAR = \"2023\"\n", 2024), None);
+    }
+
+    #[test]
+    fn adds_table_markers_once() {
+        let readme = with_table_markers("# Advent of Code").unwrap();
+        assert_eq!(readme, format!("# Advent of Code\n\n{MARKER}\n{MARKER}\n"));
+        assert_eq!(with_table_markers(&readme), None);
+        assert_eq!(
+            with_table_markers(""),
+            Some(format!("{MARKER}\n{MARKER}\n"))
+        );
+    }
+
+    #[test]
+    fn validates_templates() {
+        assert_eq!(validate_template(MODULE_TEMPLATE), Ok(()));
+        assert_eq!(
+            validate_template("use advent_of_code::solution;\nsolution!( DAY_NUMBER );"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_templates_without_macro() {
+        assert_eq!(
+            validate_template("pub fn part_one(input: &str) -> Option<u32> { None }"),
+            Err(TemplateError::MissingSolutionMacro)
+        );
+        assert_eq!(
+            validate_template("advent_of_code::solution!(1);"),
+            Err(TemplateError::MissingDayNumber)
+        );
+    }
+
+    #[test]
+    fn suggests_next_steps() {
+        assert_eq!(
+            next_steps(day!(1), None, false),
+            ["🎄 Type `cargo solve 01` to run your solution."]
+        );
+        assert_eq!(
+            next_steps(day!(1), Some(2024), true),
+            [
+                "🎄 Type `cargo solve 01` to run your solution.",
+                "🎄 Read the puzzle at https://adventofcode.com/2024/day/1",
+                "🎄 Type `cargo download 01` to download your input.",
+            ]
+        );
+    }
 }
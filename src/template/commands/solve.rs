@@ -1,11 +1,20 @@
 use std::process::{Command, Stdio};
 
+use crate::template::{logging, paths, RunOptions};
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    options: RunOptions,
+    submit_part: Option<u8>,
+    stdin: bool,
+    input: Option<String>,
+    verify_checksum: bool,
+    answers_only: bool,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), paths::bin_name(day)];
 
-    if release {
+    if options.release {
         cmd_args.push("--release".to_string());
     }
 
@@ -16,12 +25,50 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    if options.time {
         cmd_args.push("--time".to_string());
     }
 
+    if stdin {
+        cmd_args.push("--stdin".to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if answers_only {
+        cmd_args.push("--answers-only".to_string());
+    }
+
+    if verify_checksum {
+        cmd_args.push("--verify-checksum".to_string());
+    }
+
+    if options.example {
+        cmd_args.push("--example".to_string());
+    }
+
+    if let Some(part) = options.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(samples) = options.samples {
+        cmd_args.push("--samples".to_string());
+        cmd_args.push(samples.to_string());
+    }
+
+    if let Some(min_time) = options.min_time {
+        cmd_args.push("--min-time".to_string());
+        cmd_args.push(min_time.as_millis().to_string());
+    }
+
+    logging::trace_command("cargo", &cmd_args);
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .envs(options.child_envs())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::Path;
+
+use crate::template::json;
+use crate::template::logging::warning;
+use crate::template::paths;
+use crate::template::readme_benchmarks::{self, Benchmark};
+use crate::template::theme::theme;
+use crate::{all_days, Day};
+
+/// The progress of a single day, gathered from the file system and the README.
+#[derive(Debug, Clone)]
+struct DayStatus {
+    day: Day,
+    /// The solution file of the day exists.
+    solved: bool,
+    /// The input file of the day exists and is not empty.
+    has_input: bool,
+    /// The benchmark of the day last recorded in the README.
+    benchmark: Option<Benchmark>,
+}
+
+pub fn handle(json: bool) {
+    let benchmarks = match readme_benchmarks::read() {
+        Ok(benchmarks) => benchmarks,
+        Err(e) => {
+            warning!("Could not read the benchmarks from the README: {e}");
+            vec![]
+        }
+    };
+
+    let statuses: Vec<DayStatus> = all_days()
+        .map(|day| DayStatus {
+            day,
+            solved: Path::new(&paths::bin_path(day)).exists(),
+            has_input: fs::metadata(paths::input_path(day)).is_ok_and(|m| m.len() > 0),
+            benchmark: benchmarks.iter().find(|bench| bench.day == day).cloned(),
+        })
+        .collect();
+
+    if json {
+        println!("{}", to_json(&statuses));
+        return;
+    }
+
+    let theme = theme();
+    let check = |value: bool| {
+        if value {
+            format!("{}✓{}", theme.success, theme.reset)
+        } else {
+            format!("{}✗{}", theme.error, theme.reset)
+        }
+    };
+
+    println!(
+        "{}| Day | Solution | Input | Benchmark |{}",
+        theme.label, theme.reset
+    );
+    println!("| :---: | :---: | :---: | :---: |");
+
+    for status in &statuses {
+        println!(
+            "| {} | {} | {} | {} |",
+            status.day,
+            check(status.solved),
+            check(status.has_input),
+            status
+                .benchmark
+                .as_ref()
+                .map_or_else(|| "-".into(), format_benchmark)
+        );
+    }
+
+    let solved_days = statuses.iter().filter(|status| status.solved).count();
+    println!(
+        "\n{}Solved:{} {solved_days}/{} days",
+        theme.label,
+        theme.reset,
+        statuses.len()
+    );
+}
+
+/// Formats the timings of both parts of a benchmark, e.g. `1.2ms / 3.4ms`.
+fn format_benchmark(bench: &Benchmark) -> String {
+    if bench.timed_out {
+        return "Timed out".into();
+    }
+
+    let timing = |part: &Option<(String, _)>| {
+        part.as_ref()
+            .map_or_else(|| "-".into(), |(timing, _)| timing.clone())
+    };
+    format!("{} / {}", timing(&bench.part_1), timing(&bench.part_2))
+}
+
+/// Serializes the statuses to a JSON array with one object per day.
+fn to_json(statuses: &[DayStatus]) -> String {
+    let part = |part: Option<&(String, _)>| {
+        part.map_or_else(|| "null".into(), |(timing, _)| json::quote(timing))
+    };
+
+    let days: Vec<String> = statuses
+        .iter()
+        .map(|status| {
+            let benchmark = status.benchmark.as_ref();
+            format!(
+                r#"{{"day":{},"solved":{},"input":{},"part_1":{},"part_2":{},"timed_out":{}}}"#,
+                status.day.into_inner(),
+                status.solved,
+                status.has_input,
+                part(benchmark.and_then(|bench| bench.part_1.as_ref())),
+                part(benchmark.and_then(|bench| bench.part_2.as_ref())),
+                benchmark.is_some_and(|bench| bench.timed_out)
+            )
+        })
+        .collect();
+
+    format!("[{}]", days.join(","))
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_benchmark, to_json, DayStatus};
+    use crate::day;
+    use crate::template::readme_benchmarks::{Benchmark, Bytes};
+
+    fn get_benchmark() -> Benchmark {
+        Benchmark {
+            day: day!(1),
+            part_1: Some(("1.2ms".into(), Bytes(0))),
+            part_2: None,
+            total_nanos: 0_f64,
+            timed_out: false,
+            samples: [None; 2],
+        }
+    }
+
+    #[test]
+    fn formats_benchmarks() {
+        assert_eq!(format_benchmark(&get_benchmark()), "1.2ms / -");
+
+        let timed_out = Benchmark {
+            timed_out: true,
+            ..get_benchmark()
+        };
+        assert_eq!(format_benchmark(&timed_out), "Timed out");
+    }
+
+    #[test]
+    fn serializes_statuses() {
+        let statuses = [
+            DayStatus {
+                day: day!(1),
+                solved: true,
+                has_input: true,
+                benchmark: Some(get_benchmark()),
+            },
+            DayStatus {
+                day: day!(2),
+                solved: false,
+                has_input: false,
+                benchmark: None,
+            },
+        ];
+
+        assert_eq!(
+            to_json(&statuses),
+            concat!(
+                r#"[{"day":1,"solved":true,"input":true,"part_1":"1.2ms","part_2":null,"timed_out":false},"#,
+                r#"{"day":2,"solved":false,"input":false,"part_1":null,"part_2":null,"timed_out":false}]"#
+            )
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::Path;
+use std::process;
+
+use crate::template::logging::error;
+use crate::template::output::parse_part_error;
+use crate::template::readme_benchmarks::{format_significant, Benchmark, Bytes};
+use crate::template::theme::{strip_ansi, theme, Theme};
+use crate::template::{parse_output, paths, run_day_output, RunOptions};
+use crate::{Day, Part};
+
+/// The number of significant figures of the total time.
+const TOTAL_FIGURES: usize = 4;
+
+pub fn handle(day: Day, options: RunOptions) {
+    if !Path::new(&paths::bin_path(day)).exists() {
+        error!("Day {day} is not scaffolded yet.");
+        process::exit(1);
+    }
+
+    let options = options.time(true);
+    let output = match run_day_output(day, &options) {
+        Ok(output) => output,
+        Err(e) => {
+            error!("Failed to run day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let bench = parse_output(day, &output);
+    let failures: Vec<(Part, String)> = output
+        .iter()
+        .filter_map(|line| {
+            let line = strip_ansi(line);
+            parse_part_error(&line).map(|(part, reason)| (part, reason.to_string()))
+        })
+        .collect();
+
+    for line in format_timings(&bench, &failures, theme()) {
+        println!("{line}");
+    }
+
+    if !failures.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Formats the timing breakdown of a day: one line per part with its timing, sample count and
+/// heap allocation, then the total of both parts. The answers are left out.
+fn format_timings(bench: &Benchmark, failures: &[(Part, String)], theme: &Theme) -> Vec<String> {
+    let mut lines = vec![format!("{}Day {}{}", theme.day, bench.day, theme.reset)];
+
+    let mut total_bytes = 0;
+    let parts = [&bench.part_1, &bench.part_2]
+        .into_iter()
+        .zip(bench.samples);
+    for (part, (timing, samples)) in Part::ALL.into_iter().zip(parts) {
+        let label = format!("{}Part {part}:{}", theme.label, theme.reset);
+        let failure = failures.iter().find(|(failed, _)| *failed == part);
+        let line = match (timing, failure) {
+            (_, Some((_, reason))) => format!("{label} ✖ {}{reason}{}", theme.error, theme.reset),
+            (Some((time, bytes)), None) => {
+                total_bytes += bytes.0;
+                match samples {
+                    Some(samples) => format!("{label} {time} @ {samples} samples ({bytes})"),
+                    None => format!("{label} {time} ({bytes})"),
+                }
+            }
+            (None, None) => format!("{label} {}not solved{}", theme.emphasis, theme.reset),
+        };
+        lines.push(line);
+    }
+
+    lines.push(format!(
+        "{}Total:{} {}{}{} ({})",
+        theme.label,
+        theme.reset,
+        theme.emphasis,
+        format_significant(bench.total_nanos, TOTAL_FIGURES),
+        theme.reset,
+        Bytes(total_bytes)
+    ));
+    lines
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_timings;
+    use crate::template::readme_benchmarks::{Benchmark, Bytes};
+    use crate::template::theme::Theme;
+    use crate::{day, part};
+
+    #[test]
+    fn formats_timing_breakdown() {
+        let bench = Benchmark {
+            day: day!(5),
+            part_1: Some(("74.13ns".into(), Bytes(10_000))),
+            part_2: Some(("1.5ms".into(), Bytes(2_000))),
+            total_nanos: 1_500_074.13,
+            timed_out: false,
+            samples: [Some(100_000), None],
+        };
+
+        assert_eq!(
+            format_timings(&bench, &[], &Theme::plain()),
+            vec![
+                "Day 05",
+                "Part 1: 74.13ns @ 100000 samples (10 KB)",
+                "Part 2: 1.5ms (2 KB)",
+                "Total: 1.500ms (12 KB)",
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_unsolved_and_failed_parts() {
+        let bench = Benchmark {
+            day: day!(5),
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            timed_out: false,
+            samples: [None; 2],
+        };
+        let failures = [(part!(2), "invalid input".to_string())];
+
+        assert_eq!(
+            format_timings(&bench, &failures, &Theme::plain()),
+            vec![
+                "Day 05",
+                "Part 1: not solved",
+                "Part 2: ✖ invalid input",
+                "Total: 0.000ns (0 B)",
+            ]
+        );
+    }
+}
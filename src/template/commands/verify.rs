@@ -0,0 +1,167 @@
+use std::path::Path;
+use std::{fs, process};
+
+use crate::template::logging::error;
+use crate::template::output::{parse_answers, SolutionOutput};
+use crate::template::theme::theme;
+use crate::template::{paths, run_day_output, RunOptions};
+use crate::{Day, Part};
+
+/// The comparison of the answer of a part with its known answer.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+    Match,
+    Mismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+    Unknown,
+}
+
+pub fn handle(day: Day, release: bool) {
+    if !Path::new(&paths::bin_path(day)).exists() {
+        error!("Day {day} is not scaffolded yet.");
+        process::exit(1);
+    }
+
+    let known = match fs::read_to_string(paths::answers_path(day)) {
+        Ok(known) => parse_known_answers(&known),
+        Err(e) => {
+            error!(
+                "Could not read the known answers from \"{}\": {e}",
+                paths::answers_path(day)
+            );
+            process::exit(1);
+        }
+    };
+
+    let options = RunOptions::default().release(release);
+    let output = match run_day_output(day, &options) {
+        Ok(output) => output,
+        Err(e) => {
+            error!("Failed to run day {day}: {e}");
+            process::exit(1);
+        }
+    };
+    let answers = parse_answers(&output);
+
+    let theme = theme();
+    let mut has_mismatch = false;
+
+    for part in Part::ALL {
+        match verdict(known.get(part), answers.get(part)) {
+            Verdict::Match => println!(
+                "Part {part}: {}match{} ({})",
+                theme.success,
+                theme.reset,
+                answers.get(part).unwrap_or_default()
+            ),
+            Verdict::Mismatch { expected, actual } => {
+                has_mismatch = true;
+                println!(
+                    "Part {part}: {}mismatch{}, expected {expected}, got {}",
+                    theme.error,
+                    theme.reset,
+                    actual.as_deref().unwrap_or("no answer")
+                );
+            }
+            Verdict::Unknown => println!("Part {part}: no known answer"),
+        }
+    }
+
+    if has_mismatch {
+        process::exit(1);
+    }
+}
+
+fn verdict(expected: Option<&str>, actual: Option<&str>) -> Verdict {
+    match expected {
+        None => Verdict::Unknown,
+        Some(expected) if actual == Some(expected) => Verdict::Match,
+        Some(expected) => Verdict::Mismatch {
+            expected: expected.into(),
+            actual: actual.map(Into::into),
+        },
+    }
+}
+
+/// Parses a known answers file, holding the answer of part one on its first line and the answer
+/// of part two on its second line. Empty lines are parts without a known answer. The lines of
+/// multi-line answers are separated by a literal `\n`, e.g. `#..#\n####`.
+fn parse_known_answers(s: &str) -> SolutionOutput {
+    let mut lines = s.lines().map(str::trim);
+    let mut next_answer = || {
+        lines
+            .next()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.replace("\\n", "\n"))
+    };
+
+    SolutionOutput {
+        part_one: next_answer(),
+        part_two: next_answer(),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_known_answers, verdict, Verdict};
+    use crate::template::output::SolutionOutput;
+
+    #[test]
+    fn parses_known_answers() {
+        assert_eq!(
+            parse_known_answers("42\n1337\n"),
+            SolutionOutput {
+                part_one: Some("42".into()),
+                part_two: Some("1337".into()),
+            }
+        );
+        assert_eq!(
+            parse_known_answers("42"),
+            SolutionOutput {
+                part_one: Some("42".into()),
+                part_two: None,
+            }
+        );
+        assert_eq!(
+            parse_known_answers("\n1337"),
+            SolutionOutput {
+                part_one: None,
+                part_two: Some("1337".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_multi_line_known_answers() {
+        assert_eq!(
+            parse_known_answers("#..#\\n####\n42\n"),
+            SolutionOutput {
+                part_one: Some("#..#\n####".into()),
+                part_two: Some("42".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(verdict(Some("42"), Some("42")), Verdict::Match);
+        assert_eq!(verdict(None, Some("42")), Verdict::Unknown);
+        assert_eq!(
+            verdict(Some("42"), Some("41")),
+            Verdict::Mismatch {
+                expected: "42".into(),
+                actual: Some("41".into()),
+            }
+        );
+        assert_eq!(
+            verdict(Some("42"), None),
+            Verdict::Mismatch {
+                expected: "42".into(),
+                actual: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Minimal JSON support for the flat objects written by the template (e.g. the `status --json`
+//! output and the benchmark history), without pulling a serialization dependency.
+
+use std::collections::HashMap;
+
+/// A value of a flat JSON object.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+/// Quotes a string as a JSON string literal.
+pub(crate) fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Parses a JSON object whose values are all strings, numbers, booleans or `null`.
+pub(crate) fn parse_object(s: &str) -> Result<HashMap<String, Value>, String> {
+    let mut parser = Parser {
+        chars: s.trim().chars().peekable(),
+    };
+    let mut object = HashMap::new();
+
+    parser.expect('{')?;
+    if parser.eat('}') {
+        return parser.end(object);
+    }
+
+    loop {
+        let key = parser.string()?;
+        parser.expect(':')?;
+        let value = parser.value()?;
+        object.insert(key, value);
+
+        if parser.eat('}') {
+            return parser.end(object);
+        }
+        parser.expect(',')?;
+    }
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&expected).is_some()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(format!("expected `{expected}`"))
+        }
+    }
+
+    fn end<T>(&mut self, value: T) -> Result<T, String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            None => Ok(value),
+            Some(c) => Err(format!("unexpected `{c}` after the object")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+
+        loop {
+            match self.chars.next().ok_or("unterminated string")? {
+                '"' => return Ok(s),
+                '\\' => match self.chars.next().ok_or("unterminated string")? {
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'u' => {
+                        let code: String = self.chars.by_ref().take(4).collect();
+                        let c = u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid escape `\\u{code}`"))?;
+                        s.push(c);
+                    }
+                    c => s.push(c),
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'"') {
+            return self.string().map(Value::String);
+        }
+
+        let mut token = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+        {
+            token.push(c);
+        }
+
+        match token.as_str() {
+            "null" => Ok(Value::Null),
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            number => number
+                .parse()
+                .map(Value::Number)
+                .map_err(|_| format!("invalid value `{number}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_object, quote, Value};
+
+    #[test]
+    fn quotes_strings() {
+        assert_eq!(quote("1.2ms"), r#""1.2ms""#);
+        assert_eq!(quote("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+    }
+
+    #[test]
+    fn parses_objects() {
+        let object =
+            parse_object(r#"{"day": 1, "part_1": "1.2µs", "part_2": null, "ok": true}"#).unwrap();
+
+        assert_eq!(object["day"], Value::Number(1.0));
+        assert_eq!(object["part_1"], Value::String("1.2\u{b5}s".into()));
+        assert_eq!(object["part_2"], Value::Null);
+        assert_eq!(object["ok"], Value::Bool(true));
+        assert!(parse_object("{}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_quoted_strings() {
+        let quoted = quote("a\"b\\c\n");
+        let object = parse_object(&format!("{{\"s\":{quoted}}}")).unwrap();
+        assert_eq!(object["s"], Value::String("a\"b\\c\n".into()));
+    }
+
+    #[test]
+    fn rejects_invalid_objects() {
+        assert!(parse_object("").is_err());
+        assert!(parse_object(r#"{"day": 1"#).is_err());
+        assert!(parse_object(r#"{"day": one}"#).is_err());
+        assert!(parse_object(r#"{"day": 1} 2"#).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! Routes the informational and error messages of the commands.
+//!
+//! Without the `log` feature, the messages are printed to stdout (`info!`) and stderr
+//! (`warning!` and `error!`). With the `log` feature, they go through the `log` crate instead so
+//! that library consumers control their verbosity, [`init_cli_logger`] installing a logger that
+//! prints them as before.
+//!
+//! The diagnostics of `debug!` and `trace!` are only printed from the [`VERBOSE`] and
+//! [`VERY_VERBOSE`] verbosities on, set with the `-v` and `-vv` flags of the commands.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Shows the details of the commands, e.g. the lines of the readme table that changed (`-v`).
+pub const VERBOSE: u8 = 1;
+/// Also shows the child commands spawned by the commands (`-vv`).
+pub const VERY_VERBOSE: u8 = 2;
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the verbosity of the commands, `0` printing the same output as without `-v`.
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+    #[cfg(feature = "log")]
+    log::set_max_level(level_filter(verbosity));
+}
+
+/// Returns the verbosity of the commands, see [`set_verbosity`].
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Returns whether the diagnostics of `verbosity` are printed.
+pub fn is_enabled(verbosity: u8) -> bool {
+    self::verbosity() >= verbosity
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::info!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        println!($($arg)*);
+    }};
+}
+
+macro_rules! warning {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::warn!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        eprintln!($($arg)*);
+    }};
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::error!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        eprintln!($($arg)*);
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        if $crate::template::logging::is_enabled($crate::template::logging::VERBOSE) {
+            #[cfg(feature = "log")]
+            ::log::debug!($($arg)*);
+            #[cfg(not(feature = "log"))]
+            eprintln!($($arg)*);
+        }
+    }};
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => {{
+        if $crate::template::logging::is_enabled($crate::template::logging::VERY_VERBOSE) {
+            #[cfg(feature = "log")]
+            ::log::trace!($($arg)*);
+            #[cfg(not(feature = "log"))]
+            eprintln!($($arg)*);
+        }
+    }};
+}
+
+pub(crate) use {debug, error, info, warning};
+
+/// Formats a command line, quoting the arguments containing whitespace.
+pub fn command_line(program: &str, args: &[impl AsRef<str>]) -> String {
+    let mut line = program.to_string();
+    for arg in args {
+        let arg = arg.as_ref();
+        if arg.contains(char::is_whitespace) {
+            line.push_str(&format!(" \"{arg}\""));
+        } else {
+            line.push(' ');
+            line.push_str(arg);
+        }
+    }
+    line
+}
+
+/// Prints a child command before it is spawned, at the [`VERY_VERBOSE`] verbosity.
+/// Returns the printed line, if any.
+pub(crate) fn trace_command(program: &str, args: &[impl AsRef<str>]) -> Option<String> {
+    if !is_enabled(VERY_VERBOSE) {
+        return None;
+    }
+    let line = format!("Running `{}`", command_line(program, args));
+    trace!("{line}");
+    Some(line)
+}
+
+/// A logger printing info messages to stdout and warnings and errors to stderr, without any
+/// decoration.
+#[cfg(feature = "log")]
+struct CliLogger;
+
+#[cfg(feature = "log")]
+impl log::Log for CliLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= level_filter(verbosity())
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        match record.level() {
+            log::Level::Info => println!("{}", record.args()),
+            _ => eprintln!("{}", record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger used by the command line, reproducing the output of the commands without
+/// the `log` feature. Does nothing if a logger is already installed.
+#[cfg(feature = "log")]
+pub fn init_cli_logger() {
+    static LOGGER: CliLogger = CliLogger;
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level_filter(verbosity()));
+    }
+}
+
+/// The most detailed level printed at a verbosity.
+#[cfg(feature = "log")]
+fn level_filter(verbosity: u8) -> log::LevelFilter {
+    match verbosity {
+        0 => log::LevelFilter::Info,
+        VERBOSE => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    }
+}
@@ -1,48 +1,452 @@
 use crate::{Day, Part};
+use std::io::{self, Read};
+use std::path::Path;
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
+pub mod benchmark_history;
+pub mod checksum;
 pub mod commands;
+mod json;
+pub mod logging;
+pub mod output;
+pub mod paths;
 pub mod readme_benchmarks;
 pub mod runner;
+pub mod theme;
+
+pub use commands::all::{
+    parse_output, parse_output_with_warnings, run_day, run_day_captured, run_day_output,
+    CapturedOutput, ParseWarning, RunOptions,
+};
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+
+/// Reads a data file to a string. With the `compression` feature, a missing file is read from its
+/// gzip compressed sibling instead, e.g. `01.txt.gz` for `01.txt`.
+fn read_data_file(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        #[cfg(feature = "compression")]
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let mut compressed_path = path.as_os_str().to_owned();
+            compressed_path.push(".gz");
+            match fs::File::open(compressed_path) {
+                Ok(file) => decompress(file),
+                Err(_) => Err(e),
+            }
+        }
+        result => result,
+    }
+}
+
+/// Decompresses gzip compressed text.
+#[cfg(feature = "compression")]
+fn decompress(reader: impl Read) -> io::Result<String> {
+    let mut s = String::new();
+    flate2::read::GzDecoder::new(reader).read_to_string(&mut s)?;
+    Ok(s)
+}
+
+/// Returns whether an input is an `http://` or `https://` URL rather than a file path.
+fn is_url(path: &str) -> bool {
+    let scheme = path
+        .split_once("://")
+        .map(|(scheme, _)| scheme.to_ascii_lowercase());
+    matches!(scheme.as_deref(), Some("http" | "https"))
+}
+
+/// The file caching the content of a URL, in the temporary directory of the system.
+#[cfg(feature = "http")]
+fn url_cache_path(url: &str) -> std::path::PathBuf {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    env::temp_dir()
+        .join("advent_of_code")
+        .join(format!("{:016x}.txt", hasher.finish()))
+}
+
+/// Fetches the content of a URL. The content is cached on the first fetch and read from the cache
+/// afterwards, see [`url_cache_path`].
+#[cfg(feature = "http")]
+fn read_url(url: &str) -> io::Result<String> {
+    let cache_path = url_cache_path(url);
+    if let Ok(content) = fs::read_to_string(&cache_path) {
+        return Ok(content);
+    }
+
+    let content = ureq::get(url)
+        .call()
+        .map_err(io::Error::other)?
+        .into_string()?;
+    if let Some(dir) = cache_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&cache_path, &content)?;
+    Ok(content)
+}
+
+#[cfg(not(feature = "http"))]
+fn read_url(url: &str) -> io::Result<String> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("reading \"{url}\" requires the `http` feature"),
+    ))
+}
+
+/// Reads an input file, or fetches it if `path` is an `http(s)://` URL.
+fn read_path(path: &str) -> io::Result<String> {
+    if is_url(path) {
+        read_url(path)
+    } else {
+        read_data_file(Path::new(path))
+    }
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let filepath = cwd
+        .join(paths::data_dir())
+        .join(folder)
+        .join(format!("{day}.txt"));
+    let f = read_data_file(&filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads an example text file to a string.
+/// Helper function that reads the puzzle input of a day to a string.
+///
+/// # Panics
+/// Panics if the input file can not be read, see [`read_input_checked`] for a non-panicking variant.
 #[must_use]
-pub fn read_example(day: Day, part: Part) -> String {
+pub fn read_input(day: Day) -> String {
+    read_input_checked(day).expect("could not open input file")
+}
+
+/// Helper function that reads the puzzle input of a day to a string,
+/// returning an error if the input file can not be read.
+pub fn read_input_checked(day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    read_data_file(&cwd.join(paths::input_path(day)))
+}
+
+/// The first line of an example file holding its expected answer, e.g. `# answer: 42`.
+const EXAMPLE_ANSWER_HEADER: &str = "# answer:";
+
+/// Splits an example into its expected answer, if it starts with an answer header, and its body.
+fn split_example_answer(example: &str) -> (Option<&str>, &str) {
+    let (first_line, body) = example.split_once('\n').unwrap_or((example, ""));
+
+    match first_line.trim_end().strip_prefix(EXAMPLE_ANSWER_HEADER) {
+        Some(answer) => (Some(answer.trim()), body),
+        None => (None, example),
+    }
+}
+
+/// Picks the example of a part, falling back to the shared example if the example of the part is
+/// missing or empty.
+fn select_example(
+    part_example: io::Result<String>,
+    shared_example: impl FnOnce() -> io::Result<String>,
+) -> io::Result<String> {
+    match part_example {
+        Ok(example) if !example.trim().is_empty() => Ok(example),
+        Ok(example) => shared_example().or(Ok(example)),
+        Err(e) => shared_example().map_err(|_| e),
+    }
+}
+
+fn read_example_file(day: Day, part: Part) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data/examples").join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = select_example(
+        read_data_file(&cwd.join(paths::example_path(day, part))),
+        || read_data_file(&cwd.join(paths::shared_example_path(day))),
+    );
     f.expect("could not open input file")
 }
 
+/// Helper function that reads an example text file to a string.
+/// The example of a part is read from `data/examples/{day}-{part}.txt`, or from the shared
+/// `data/examples/{day}.txt` if the former is missing or empty.
+/// The answer header of the example (e.g. `# answer: 42`) is not part of the returned string.
+#[must_use]
+pub fn read_example(day: Day, part: Part) -> String {
+    let example = read_example_file(day, part);
+    split_example_answer(&example).1.to_string()
+}
+
+/// Helper function that reads the expected answer of an example, given on its first line as
+/// `# answer: 42`. Returns [`None`] if the example has no answer header.
+#[must_use]
+pub fn read_example_answer(day: Day, part: Part) -> Option<String> {
+    let example = read_example_file(day, part);
+    split_example_answer(&example).0.map(Into::into)
+}
+
+/// Helper function that reads the whole standard input to a string.
+#[must_use]
+pub fn read_stdin() -> String {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .expect("could not read standard input");
+    input
+}
+
+/// The environment variable holding the seed given to `cargo solve` or `cargo all` with `--seed`.
+pub const SEED_VAR: &str = "AOC_SEED";
+
+/// Returns the seed given to `cargo solve` or `cargo all` with `--seed`, so that solutions relying
+/// on randomness can seed their random number generator and run reproducibly. Returns [`None`]
+/// when no seed was given.
+///
+/// # Panics
+/// Panics if [`SEED_VAR`] is set to something other than an unsigned integer.
+#[must_use]
+pub fn seed() -> Option<u64> {
+    let seed = env::var(SEED_VAR).ok()?;
+    match seed.parse() {
+        Ok(seed) => Some(seed),
+        Err(_) => panic!("{SEED_VAR} must be an unsigned integer, got \"{seed}\""),
+    }
+}
+
+/// Where the input of a solution is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The file passed with `--input <path>`, or the URL passed with `--input <url>`.
+    File(String),
+    /// The standard input, with `--stdin`.
+    Stdin,
+    /// The puzzle input of the day, `data/inputs/{day}.txt`.
+    Day,
+}
+
+impl InputSource {
+    /// Resolves the input source from the arguments of a solution binary.
+    /// `--input <path>` takes precedence over `--stdin`, which takes precedence over the puzzle
+    /// input of the day.
+    pub fn from_args(args: &[String]) -> Self {
+        if let Some(index) = args.iter().position(|x| x == "--input") {
+            match args.get(index + 1) {
+                Some(path) => return Self::File(path.clone()),
+                None => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 --input path/to/input.txt");
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        if args.iter().any(|x| x == "--stdin") {
+            Self::Stdin
+        } else {
+            Self::Day
+        }
+    }
+
+    /// Reads the input of `day` from the source.
+    ///
+    /// # Panics
+    /// Panics if the input can not be read.
+    #[must_use]
+    pub fn read(&self, day: Day) -> String {
+        match self {
+            Self::File(path) => read_path(path)
+                .unwrap_or_else(|e| panic!("could not open input file \"{path}\": {e}")),
+            Self::Stdin => read_stdin(),
+            Self::Day => read_input(day),
+        }
+    }
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// The input is read from `data/inputs/{day}.txt`, unless the binary is called with the `--stdin`
+/// flag, in which case it is read from the standard input instead. The `--input <path>` option
+/// reads it from another file and takes precedence over `--stdin`, see [`InputSource`]. Answers
+/// of such a file are never submitted.
+/// With the `--example` flag, each part runs against its own example file from `data/examples`
+/// and the answers are never submitted.
+/// With the `--answers-only` flag, only the answers of both parts are printed, one per line, see
+/// [`runner::print_answers`].
+/// With the `--verify-checksum` flag, the puzzle input is verified against the checksum recorded
+/// when it was downloaded, see [`checksum`].
+/// The seed given with `--seed` is read by the solution with [`seed`].
+/// The heap usage of the parts is only reported when the solution sets
+/// [`alloc::CountingAllocator`] as its global allocator.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
-
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            if std::env::args().any(|x| x == "--example") {
+                let example_one =
+                    advent_of_code::template::read_example(DAY, advent_of_code::part!(1));
+                let example_two =
+                    advent_of_code::template::read_example(DAY, advent_of_code::part!(2));
+                if answers_only() {
+                    print_answers([
+                        answer_part(part_one, &example_one, 1),
+                        answer_part(part_two, &example_two, 2),
+                    ]);
+                    return;
+                }
+                run_part(part_one, &example_one, 1);
+                run_part(part_two, &example_two, 2);
+                return;
+            }
+
+            let args: Vec<String> = std::env::args().collect();
+            let source = advent_of_code::template::InputSource::from_args(&args);
+            let input = source.read(DAY);
+            if matches!(source, advent_of_code::template::InputSource::Day)
+                && args.iter().any(|x| x == "--verify-checksum")
+            {
+                advent_of_code::template::checksum::warn_on_mismatch(DAY, &input);
+            }
+            if answers_only() {
+                print_answers([
+                    answer_part(part_one, &input, 1),
+                    answer_part(part_two, &input, 2),
+                ]);
+                return;
+            }
+            let output = advent_of_code::template::output::SolutionOutput {
+                part_one: run_part(part_one, &input, 1),
+                part_two: run_part(part_two, &input, 2),
+            };
+            // the answers of another input are not the answers of the puzzle.
+            if !matches!(source, advent_of_code::template::InputSource::File(_)) {
+                submit_output(&output, DAY);
+            }
         }
     };
 }
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io;
+
+    use super::{is_url, select_example, split_example_answer, InputSource};
+
+    #[test]
+    fn resolves_input_source() {
+        let source = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            InputSource::from_args(&args)
+        };
+
+        assert_eq!(source(&["01", "--time"]), InputSource::Day);
+        assert_eq!(source(&["01", "--stdin"]), InputSource::Stdin);
+        assert_eq!(
+            source(&["01", "--input", "inputs/friend.txt"]),
+            InputSource::File("inputs/friend.txt".into())
+        );
+        // `--input` takes precedence over `--stdin`, wherever they are.
+        assert_eq!(
+            source(&["01", "--stdin", "--input", "friend.txt"]),
+            InputSource::File("friend.txt".into())
+        );
+        assert_eq!(
+            source(&["01", "--input", "friend.txt", "--stdin"]),
+            InputSource::File("friend.txt".into())
+        );
+    }
+
+    #[test]
+    fn detects_urls() {
+        assert!(is_url("https://example.com/inputs/01.txt"));
+        assert!(is_url("HTTP://example.com/01"));
+        assert!(!is_url("inputs/friend.txt"));
+        assert!(!is_url("ftp://example.com/01.txt"));
+        assert!(!is_url("https:/example.com"));
+        assert!(!is_url("data/https://example.com"));
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn caches_urls_separately() {
+        let first = super::url_cache_path("https://example.com/01.txt");
+        assert_eq!(first, super::url_cache_path("https://example.com/01.txt"));
+        assert_ne!(first, super::url_cache_path("https://example.com/02.txt"));
+        assert!(first.starts_with(std::env::temp_dir()));
+    }
+
+    #[test]
+    #[cfg(not(feature = "http"))]
+    fn requires_http_feature() {
+        let e = super::read_path("https://example.com/01.txt").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn splits_example_answer() {
+        assert_eq!(
+            split_example_answer("# answer: 42\n1 2\n3 4\n"),
+            (Some("42"), "1 2\n3 4\n")
+        );
+        assert_eq!(
+            split_example_answer("# answer:abc\r\n1 2\r\n"),
+            (Some("abc"), "1 2\r\n")
+        );
+        assert_eq!(split_example_answer("# answer: 42"), (Some("42"), ""));
+    }
+
+    #[test]
+    fn keeps_examples_without_answer() {
+        assert_eq!(split_example_answer("1 2\n3 4\n"), (None, "1 2\n3 4\n"));
+        assert_eq!(split_example_answer("# 1 2\n"), (None, "# 1 2\n"));
+        assert_eq!(split_example_answer(""), (None, ""));
+    }
+
+    #[test]
+    fn selects_part_example() {
+        let example = select_example(Ok("1 2\n".into()), || Ok("3 4\n".into()));
+        assert_eq!(example.unwrap(), "1 2\n");
+    }
+
+    #[test]
+    fn falls_back_to_shared_example() {
+        let missing = || Err(io::Error::from(io::ErrorKind::NotFound));
+
+        let example = select_example(missing(), || Ok("3 4\n".into()));
+        assert_eq!(example.unwrap(), "3 4\n");
+        let example = select_example(Ok("\n".into()), || Ok("3 4\n".into()));
+        assert_eq!(example.unwrap(), "3 4\n");
+
+        // an empty example is still read if there is no shared example.
+        let example = select_example(Ok(String::new()), missing);
+        assert_eq!(example.unwrap(), "");
+        let example = select_example(missing(), missing);
+        assert_eq!(example.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn decompresses_gzip() {
+        use std::io::Write;
+
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"1 2\n3 4\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(
+            super::decompress(compressed.as_slice()).unwrap(),
+            "1 2\n3 4\n"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+/// The answers produced by a solution binary.
+use crate::template::theme::strip_ansi;
+use crate::{part, Part};
+
+/// The answers of both parts of a solution, [`None`] for the parts that produced no answer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolutionOutput {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl SolutionOutput {
+    /// Returns the answer of a part, if any.
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part.into_inner() {
+            1 => self.part_one.as_deref(),
+            _ => self.part_two.as_deref(),
+        }
+    }
+
+    /// Returns an iterator over the parts that produced an answer.
+    pub fn iter(&self) -> Iter<'_> {
+        self.into_iter()
+    }
+}
+
+/// An iterator over the `(Part, answer)` pairs of a [`SolutionOutput`].
+pub type Iter<'a> = std::iter::Flatten<std::array::IntoIter<Option<(Part, &'a str)>, 2>>;
+
+impl<'a> IntoIterator for &'a SolutionOutput {
+    type Item = (Part, &'a str);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        [
+            self.part_one.as_deref().map(|answer| (part!(1), answer)),
+            self.part_two.as_deref().map(|answer| (part!(2), answer)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Extracts the answers printed by a solution, e.g. `42` from `Part 1: 42 (1.2µs) (10 B)`.
+///
+/// Answers spanning several lines are printed as a block: a `Part N: ▼ (timing) (bytes)` line,
+/// followed by the lines of the answer up to the first blank line, e.g.
+///
+/// ```text
+/// Part 2: ▼ (2.0µs) (0 B)
+/// #..#
+/// ####
+///
+/// ```
+///
+/// The lines of such an answer are joined with `\n`. The output lines may contain ANSI escape
+/// codes and intermediate results, only the text after the last carriage return is considered.
+pub fn parse_answers(output: &[String]) -> SolutionOutput {
+    let mut answers = SolutionOutput::default();
+    // the final result of a part is printed after a carriage return.
+    let mut lines = output
+        .iter()
+        .map(|line| strip_ansi(line.rsplit('\r').next().unwrap_or_default()))
+        .peekable();
+
+    while let Some(line) = lines.next() {
+        let Some((part, answer)) = parse_answer_line(&line) else {
+            continue;
+        };
+
+        let answer = if answer.is_empty() {
+            let mut block = vec![];
+            // a block ends at a blank line, or at the next part if the blank line is missing.
+            while let Some(line) =
+                lines.next_if(|line| !line.trim().is_empty() && !line.starts_with("Part "))
+            {
+                block.push(line);
+            }
+            (!block.is_empty()).then(|| block.join("\n"))
+        } else {
+            Some(answer.to_string())
+        };
+
+        match part.into_inner() {
+            1 => answers.part_one = answer,
+            _ => answers.part_two = answer,
+        }
+    }
+
+    answers
+}
+
+/// Extracts the part and the reason of a `Part N: ✖ error: <reason>` line printed by a solution
+/// whose part returned an error, e.g. `(1, "invalid digit found in string")`.
+///
+/// Only the text after the last carriage return is considered, see [`parse_answer_line`]. The line
+/// must not contain ANSI escape codes. Returns [`None`] for any other line.
+pub fn parse_part_error(line: &str) -> Option<(Part, &str)> {
+    let line = line.rsplit('\r').next()?;
+    let (part, rest) = line.strip_prefix("Part ")?.split_once(": ")?;
+    let part = part.parse().ok()?;
+    let reason = rest.strip_prefix("✖ error: ")?;
+
+    Some((part, reason.trim_end()))
+}
+
+/// Extracts the part and the answer of a `Part N: <answer> (timing) (bytes)` line printed by a
+/// solution, e.g. `(1, "42")` for `Part 1: 42 (1.2µs @ 100 samples) (10 B)`.
+///
+/// Only the text after the last carriage return is considered, as the runner prints the final line
+/// of a part over its intermediate one. The line must not contain ANSI escape codes. Returns
+/// [`None`] for unsolved parts (`✖`) and lines that are not part lines. Answers spanning several
+/// lines are printed on the following lines after a `▼` marker, an empty answer is returned for
+/// them.
+pub fn parse_answer_line(line: &str) -> Option<(Part, &str)> {
+    let line = line.rsplit('\r').next()?;
+    let (part, rest) = line.strip_prefix("Part ")?.split_once(": ")?;
+    let part = part.parse().ok()?;
+
+    if rest.starts_with('✖') {
+        return None;
+    }
+    if rest.starts_with('▼') {
+        return Some((part, ""));
+    }
+
+    let mut answer = rest.trim_end();
+    // the heap allocation group is only printed by timed runs.
+    if let Some((head, _)) = answer
+        .rsplit_once(" (")
+        .filter(|(_, group)| group.ends_with("B)") || group.ends_with("b)"))
+    {
+        answer = head;
+    }
+    // the timing group.
+    if let Some((head, _)) = answer
+        .rsplit_once(" (")
+        .filter(|(_, group)| group.ends_with(')'))
+    {
+        answer = head;
+    }
+
+    Some((part, answer))
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answer_line, parse_answers, parse_part_error, SolutionOutput};
+    use crate::part;
+
+    #[test]
+    fn parses_part_errors() {
+        assert_eq!(
+            parse_part_error("Part 2: ✖\rPart 2: ✖ error: empty input             "),
+            Some((part!(2), "empty input"))
+        );
+        assert_eq!(parse_part_error("Part 2: ✖             "), None);
+        assert_eq!(parse_part_error("Part 2: 42 (1.0µs)"), None);
+        assert_eq!(parse_part_error("error: empty input"), None);
+    }
+
+    #[test]
+    fn parses_answers() {
+        let output: Vec<String> = [
+            "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (74.13ns @ 100 samples) (10KB)",
+            "Part 2: ✖\rPart 2: ✖             ",
+            "",
+        ]
+        .map(Into::into)
+        .to_vec();
+
+        assert_eq!(
+            parse_answers(&output),
+            SolutionOutput {
+                part_one: Some("42".into()),
+                part_two: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_answers_with_parentheses() {
+        let output: Vec<String> = ["Part 1: f(x) (1.0µs) (0 B)", "Part 2: 4 (2.0µs) (0 B)"]
+            .map(Into::into)
+            .to_vec();
+
+        assert_eq!(
+            parse_answers(&output),
+            SolutionOutput {
+                part_one: Some("f(x)".into()),
+                part_two: Some("4".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_multi_line_answers() {
+        let output: Vec<String> = [
+            "Part 1: ▼ \rPart 1: ▼  (1.0µs) (16 B)",
+            "#..#",
+            "####",
+            "",
+            "Part 2: ▼  (2.0µs) (0 B)",
+            ".##.",
+            "Part 3: not an answer",
+        ]
+        .map(Into::into)
+        .to_vec();
+
+        assert_eq!(
+            parse_answers(&output),
+            SolutionOutput {
+                part_one: Some("#..#\n####".into()),
+                part_two: Some(".##.".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn iterates_over_answers() {
+        let output = SolutionOutput {
+            part_one: Some("42".into()),
+            part_two: Some("24".into()),
+        };
+        let answers: Vec<_> = output.iter().collect();
+        assert_eq!(answers, vec![(part!(1), "42"), (part!(2), "24")]);
+    }
+
+    #[test]
+    fn skips_missing_answers() {
+        let output = SolutionOutput {
+            part_one: None,
+            part_two: Some("24".into()),
+        };
+        let answers: Vec<_> = output.iter().collect();
+        assert_eq!(answers, vec![(part!(2), "24")]);
+
+        assert_eq!(SolutionOutput::default().iter().count(), 0);
+    }
+
+    #[test]
+    fn parses_answer_lines() {
+        let lines = [
+            (
+                "Part 1: 5 (74.13ns @ 100 samples) (10KB)",
+                Some((part!(1), "5")),
+            ),
+            (
+                "Part 2: 10 (74.13ms @ 99999 samples) (10KB)",
+                Some((part!(2), "10")),
+            ),
+            (
+                "Part 1: 5 (1,234.56ms @ 100 samples) (10KB)",
+                Some((part!(1), "5")),
+            ),
+            (
+                "Part 2: 5 (2.5\u{3bc}s @ 100 samples) (10 KiB)",
+                Some((part!(2), "5")),
+            ),
+            ("Part 1: 5 (0ns @ 0 samples) (0B)", Some((part!(1), "5"))),
+            ("Part 1: 42 (1.2ms)", Some((part!(1), "42"))),
+            ("Part 1: f(x) (1.0\u{b5}s) (0 B)", Some((part!(1), "f(x)"))),
+            ("Part 1: a b (1.0\u{b5}s) (0 B)", Some((part!(1), "a b"))),
+            (
+                "Part 1: 42 > benching\rPart 1: 42 (1.0\u{b5}s @ 10 samples) (0 B)",
+                Some((part!(1), "42")),
+            ),
+            ("Part 2: ▼  (2.0\u{b5}s) (0 B)", Some((part!(2), ""))),
+        ];
+        for (line, expected) in lines {
+            assert_eq!(parse_answer_line(line), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn skips_other_lines() {
+        assert_eq!(parse_answer_line("Part 2: ✖\rPart 2: ✖             "), None);
+        assert_eq!(parse_answer_line("Part 3: 5 (1.0ms)"), None);
+        assert_eq!(parse_answer_line("some debug output"), None);
+        assert_eq!(parse_answer_line(""), None);
+    }
+}
@@ -0,0 +1,227 @@
+/// Centralizes the paths of the files used by the template.
+///
+/// The data and solution directories can be relocated with the `AOC_DATA_DIR` and `AOC_BIN_DIR`
+/// environment variables, they default to `data` and `src/bin`. The solution files are named
+/// following the `AOC_BIN_NAME` naming scheme, `01.rs` by default. Repositories holding several
+/// years keep the data of each year in a `{year}` namespace of the data directory, e.g.
+/// `data/2023`, see [`year_data_dir`].
+use std::env;
+
+use crate::{Day, Part};
+
+const DEFAULT_DATA_DIR: &str = "data";
+const DEFAULT_BIN_DIR: &str = "src/bin";
+
+fn dir_from_env(var: &str, default: &str) -> String {
+    match env::var(var) {
+        Ok(dir) if !dir.is_empty() => dir.trim_end_matches('/').to_string(),
+        _ => default.to_string(),
+    }
+}
+
+/// The root directory of the inputs, examples, answers and puzzles.
+#[must_use]
+pub fn data_dir() -> String {
+    dir_from_env("AOC_DATA_DIR", DEFAULT_DATA_DIR)
+}
+
+/// The directory containing the solution binaries.
+#[must_use]
+pub fn bin_dir() -> String {
+    dir_from_env("AOC_BIN_DIR", DEFAULT_BIN_DIR)
+}
+
+/// The data directory of a year in a repository holding several years, e.g. `data/2023`.
+#[must_use]
+pub fn year_data_dir(data_dir: &str, year: u16) -> String {
+    format!("{data_dir}/{year}")
+}
+
+/// The directory containing the puzzle inputs.
+#[must_use]
+pub fn inputs_dir() -> String {
+    format!("{}/inputs", data_dir())
+}
+
+/// The directory containing the puzzle examples.
+#[must_use]
+pub fn examples_dir() -> String {
+    format!("{}/examples", data_dir())
+}
+
+/// The directory containing the known answers of the puzzles.
+#[must_use]
+pub fn answers_dir() -> String {
+    format!("{}/answers", data_dir())
+}
+
+/// The directory containing the puzzle descriptions.
+#[must_use]
+pub fn puzzles_dir() -> String {
+    format!("{}/puzzles", data_dir())
+}
+
+#[must_use]
+pub fn input_path(day: Day) -> String {
+    format!("{}/{day}.txt", inputs_dir())
+}
+
+/// The SHA-256 checksum of the downloaded input of a day, see [`crate::template::checksum`].
+#[must_use]
+pub fn checksum_path(day: Day) -> String {
+    format!("{}/{day}.sha256", inputs_dir())
+}
+
+#[must_use]
+pub fn example_path(day: Day, part: Part) -> String {
+    format!("{}/{day}-{part}.txt", examples_dir())
+}
+
+/// The example file shared by both parts, used when a part has no example of its own.
+#[must_use]
+pub fn shared_example_path(day: Day) -> String {
+    format!("{}/{day}.txt", examples_dir())
+}
+
+#[must_use]
+pub fn answers_path(day: Day) -> String {
+    format!("{}/{day}.txt", answers_dir())
+}
+
+/// The file the benchmarks are appended to with `all --store`, one JSON record per line.
+#[must_use]
+pub fn history_path() -> String {
+    "benchmarks/history.jsonl".into()
+}
+
+#[must_use]
+pub fn puzzle_path(day: Day) -> String {
+    format!("{}/{day}.md", puzzles_dir())
+}
+
+/// The naming scheme of the solution files, with `{day}` replaced by the day number,
+/// `{day_padded}` by the two digit day number and `{year}` by the year of the puzzles.
+pub const DEFAULT_BIN_NAME: &str = "{day_padded}.rs";
+
+/// The naming scheme of the solution files, set with the `AOC_BIN_NAME` environment variable, e.g.
+/// `day{day_padded}.rs`. Schemes without a day placeholder fall back to [`DEFAULT_BIN_NAME`].
+#[must_use]
+pub fn bin_name_pattern() -> String {
+    match env::var("AOC_BIN_NAME") {
+        Ok(pattern) if pattern.contains("{day}") || pattern.contains("{day_padded}") => pattern,
+        _ => DEFAULT_BIN_NAME.into(),
+    }
+}
+
+/// The file name of the solution of a day following a naming scheme, `.rs` being appended to
+/// schemes that do not end with it. The year is read from the `AOC_YEAR` environment variable.
+#[must_use]
+pub fn format_bin_file(pattern: &str, day: Day) -> String {
+    let year = env::var("AOC_YEAR").unwrap_or_default();
+    format_year_bin_file(pattern, day, &year)
+}
+
+/// Same as [`format_bin_file`] for the solutions of a given year.
+#[must_use]
+pub fn format_year_bin_file(pattern: &str, day: Day, year: &str) -> String {
+    let file = pattern
+        .replace("{day_padded}", &day.to_string())
+        .replace("{day}", &day.into_inner().to_string())
+        .replace("{year}", year);
+    if file.ends_with(".rs") {
+        file
+    } else {
+        format!("{file}.rs")
+    }
+}
+
+/// Returns the day of a solution file name following a naming scheme, [`None`] if the file does
+/// not follow it.
+#[must_use]
+pub fn parse_bin_file(pattern: &str, file: &str) -> Option<Day> {
+    crate::all_days().find(|day| format_bin_file(pattern, *day) == file)
+}
+
+/// Same as [`parse_bin_file`] for the solutions of a given year.
+#[must_use]
+pub fn parse_year_bin_file(pattern: &str, file: &str, year: &str) -> Option<Day> {
+    crate::all_days().find(|day| format_year_bin_file(pattern, *day, year) == file)
+}
+
+/// The name of the cargo binary of a day, i.e. its file name without the `.rs` extension.
+#[must_use]
+pub fn bin_name(day: Day) -> String {
+    let file = format_bin_file(&bin_name_pattern(), day);
+    file.trim_end_matches(".rs").to_string()
+}
+
+#[must_use]
+pub fn bin_path(day: Day) -> String {
+    format!(
+        "{}/{}",
+        bin_dir(),
+        format_bin_file(&bin_name_pattern(), day)
+    )
+}
+
+/// The name of the cargo binary of a day of another year than the current one.
+#[must_use]
+pub fn year_bin_name(day: Day, year: u16) -> String {
+    let file = format_year_bin_file(&bin_name_pattern(), day, &year.to_string());
+    file.trim_end_matches(".rs").to_string()
+}
+
+/// The path of the solution of a day of another year than the current one.
+#[must_use]
+pub fn year_bin_path(day: Day, year: u16) -> String {
+    format!(
+        "{}/{}",
+        bin_dir(),
+        format_year_bin_file(&bin_name_pattern(), day, &year.to_string())
+    )
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bin_file, format_year_bin_file, parse_bin_file, DEFAULT_BIN_NAME};
+    use crate::day;
+
+    #[test]
+    fn formats_default_bin_files() {
+        assert_eq!(format_bin_file(DEFAULT_BIN_NAME, day!(1)), "01.rs");
+        assert_eq!(format_bin_file(DEFAULT_BIN_NAME, day!(25)), "25.rs");
+    }
+
+    #[test]
+    fn formats_custom_bin_files() {
+        assert_eq!(format_bin_file("day{day_padded}.rs", day!(3)), "day03.rs");
+        assert_eq!(format_bin_file("day_{day}", day!(3)), "day_3.rs");
+        assert_eq!(format_bin_file("{day}_{day_padded}", day!(12)), "12_12.rs");
+    }
+
+    #[test]
+    fn formats_year_bin_files() {
+        assert_eq!(
+            format_year_bin_file("y{year}_{day_padded}", day!(3), "2022"),
+            "y2022_03.rs"
+        );
+        assert_eq!(
+            format_year_bin_file(DEFAULT_BIN_NAME, day!(3), "2022"),
+            "03.rs"
+        );
+    }
+
+    #[test]
+    fn parses_bin_files() {
+        assert_eq!(parse_bin_file(DEFAULT_BIN_NAME, "07.rs"), Some(day!(7)));
+        assert_eq!(
+            parse_bin_file("day{day_padded}.rs", "day07.rs"),
+            Some(day!(7))
+        );
+        assert_eq!(parse_bin_file("day_{day}", "day_7.rs"), Some(day!(7)));
+        assert_eq!(parse_bin_file("day_{day}", "day_07.rs"), None);
+        assert_eq!(parse_bin_file(DEFAULT_BIN_NAME, "26.rs"), None);
+        assert_eq!(parse_bin_file(DEFAULT_BIN_NAME, "main.rs"), None);
+    }
+}
@@ -1,15 +1,26 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::commands::all::parse_duration;
+use crate::template::logging::{info, warning};
+use crate::template::paths;
+use crate::template::theme::theme;
+use crate::{all_days, Day};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+pub(crate) static MARKER: &str = "<!--- benchmarking table --->";
+static BADGE_MARKER: &str = "<!--- progress badge --->";
 
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
     IO(io::Error),
+    DuplicateDay(Day),
 }
 
 impl From<std::io::Error> for Error {
@@ -18,12 +29,53 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse the README: {e}"),
+            Error::IO(e) => write!(f, "could not read or write the README: {e}"),
+            Error::DuplicateDay(day) => write!(f, "day {day} is benchmarked more than once"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(e) => Some(e),
+            Error::Parser(_) | Error::DuplicateDay(_) => None,
+        }
+    }
+}
+
+/// An amount of heap allocated bytes, displayed with the most appropriate unit (e.g. `10 KB`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#.1}", byte_unit::Byte::from_u64(self.0))
+    }
+}
+
+impl FromStr for Bytes {
+    type Err = byte_unit::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        byte_unit::Byte::parse_str(s, true).map(|byte| Self(byte.as_u64()))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Benchmark {
     pub day: Day,
-    pub part_1: Option<(String, String)>,
-    pub part_2: Option<(String, String)>,
+    pub part_1: Option<(String, Bytes)>,
+    pub part_2: Option<(String, Bytes)>,
     pub total_nanos: f64,
+    /// The solution was killed before it finished, its parts are displayed as `Timed out`.
+    pub timed_out: bool,
+    /// The number of samples the timings of part one and part two were measured over, if known.
+    pub samples: [Option<u128>; 2],
 }
 
 pub struct TablePosition {
@@ -31,78 +83,885 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
+/// The path of a solution binary as linked from the README.
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    relative_link(paths::bin_path(day))
+}
+
+/// Prefixes relative paths with `./`, the way they are linked from the README.
+fn relative_link(path: String) -> String {
+    if Path::new(&path).is_relative() && !path.starts_with("./") {
+        format!("./{path}")
+    } else {
+        path
+    }
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+    locate_markers(readme, MARKER, "table")
+}
+
+fn locate_markers(readme: &str, marker: &str, name: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "Too many occurences of {name} marker in README."
+        )));
     }
 
     let pos_start = matches
         .first()
         .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("Could not find {name} start position.")))?;
 
     let pos_end = matches
         .last()
         .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("Could not find {name} end position.")))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, benchmarks: Vec<Benchmark>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Builds a markdown image of a shields.io badge displaying the number of solved days,
+/// e.g. `![Progress](https://img.shields.io/badge/progress-13%2F25%20days-yellow)`.
+pub fn construct_badge(solved_days: usize) -> String {
+    let color = match solved_days {
+        0 => "red",
+        25.. => "brightgreen",
+        _ => "yellow",
+    };
+    format!("![Progress](https://img.shields.io/badge/progress-{solved_days}%2F25%20days-{color})")
+}
+
+/// Replaces the progress badge of `s`, if the README contains the badge markers.
+fn update_badge(s: &mut String, solved_days: usize) -> Result<(), Error> {
+    if !s.contains(BADGE_MARKER) {
+        return Ok(());
+    }
+
+    let positions = locate_markers(s, BADGE_MARKER, "badge")?;
+    let badge = format!(
+        "{BADGE_MARKER}{}{BADGE_MARKER}",
+        construct_badge(solved_days)
+    );
+    s.replace_range(positions.pos_start..positions.pos_end, &badge);
+    Ok(())
+}
+
+/// Counts the days that have a solution in the bin directory.
+fn count_solved_days() -> usize {
+    all_days()
+        .filter(|day| Path::new(&paths::bin_path(*day)).exists())
+        .count()
+}
+
+/// The label of a day in the table and the ranking, `[Day N](path)` linking to the solution of
+/// the day or plain `Day N` text.
+fn day_label(day: Day, links: bool) -> String {
+    if links {
+        format!("[Day {}]({})", day.into_inner(), get_path_for_bin(day))
+    } else {
+        format!("Day {}", day.into_inner())
+    }
+}
+
+/// Same as [`day_label`] for a day of the given year, linking to the solution of that year.
+fn year_day_label(day: Day, year: u16, links: bool) -> String {
+    if links {
+        let path = relative_link(paths::year_bin_path(day, year));
+        format!("[Day {}]({path})", day.into_inner())
+    } else {
+        format!("Day {}", day.into_inner())
+    }
+}
+
+/// Builds the benchmarking table without touching the file system.
+///
+/// The returned string starts and ends with the benchmarking table marker and contains, in order:
+///  1. a `{prefix} {title}` header line (e.g. `## Benchmarks` for a `##` prefix and a
+///     `Benchmarks` title),
+///  2. a markdown table with one `| [Day N](path) | `time` / `bytes` | `time` / `bytes` |` row per
+///     benchmark, missing parts being displayed as `-` and parts of timed out days as `Timed out`.
+///     Without `style.links`, days are displayed as plain `Day N` text. With `style.samples`,
+///     the timing of a part is followed by the number of samples it was measured over, e.g.
+///     `` `10ms` (n=100) / `10 B` ``. With `style.weeks`, there is one such table per week
+///     instead, under a `{prefix}# Week N (days A-B)` header and followed by a
+///     `_Week N total: time / bytes_` subtotal line,
+///  3. the total time and total allocations footers, unless `total` hides them. The total time
+///     is labelled `Total time` unless `total` sets another label.
+///
+/// Lines are separated by `\n`, the string does not end with a line break.
+pub fn construct_table(
+    prefix: &str,
+    title: &str,
+    benchmarks: Vec<Benchmark>,
+    total_millis: f64,
+    style: TableStyle,
+    total: &TotalOptions,
+) -> String {
+    let mut lines: Vec<String> = vec![MARKER.into(), format!("{prefix} {title}"), String::new()];
+    lines.extend(table_lines(
+        prefix,
+        benchmarks,
+        total_millis,
+        style,
+        total,
+        |day| day_label(day, style.links),
+    ));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// How the benchmarks are rendered by [`construct_table`] and [`construct_year_tables`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TableStyle {
+    /// Links each day to its solution file, instead of displaying it as plain `Day N` text.
+    pub links: bool,
+    /// Follows the timing of each part with the number of samples it was measured over.
+    pub samples: bool,
+    /// Splits the table into one table per week, days 1 to 7, 8 to 14 and so on, each followed by
+    /// the subtotal of its week.
+    pub weeks: bool,
+}
+
+/// The benchmarks of one year, displayed in their own table by [`construct_year_tables`].
+#[derive(Debug, Clone)]
+pub struct YearBenchmarks {
+    pub year: u16,
+    pub benchmarks: Vec<Benchmark>,
+    pub total_millis: f64,
+}
+
+/// Builds the benchmarking table of a repository holding several years, without touching the file
+/// system.
+///
+/// The returned string is laid out like the one of [`construct_table`], except that the
+/// `{prefix} {title}` header is followed by one `{prefix}# {year}` section per year, in the
+/// given order. Each section holds the table of its year with its own total footers, the days
+/// linking to the solutions of that year.
+pub fn construct_year_tables(
+    prefix: &str,
+    title: &str,
+    years: Vec<YearBenchmarks>,
+    style: TableStyle,
+    total: &TotalOptions,
+) -> String {
+    let mut lines: Vec<String> = vec![MARKER.into(), format!("{prefix} {title}"), String::new()];
+    for YearBenchmarks {
+        year,
+        benchmarks,
+        total_millis,
+    } in years
+    {
+        let year_prefix = format!("{prefix}#");
+        lines.push(format!("{year_prefix} {year}"));
+        lines.push(String::new());
+        lines.extend(table_lines(
+            &year_prefix,
+            benchmarks,
+            total_millis,
+            style,
+            total,
+            |day| year_day_label(day, year, style.links),
+        ));
+    }
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// The number of days of a week section, see [`TableStyle::weeks`].
+const WEEK_DAYS: u8 = 7;
+
+/// The lines of the markdown tables of benchmarks followed by their total footers, see
+/// [`construct_table`]. The week sections are headed with a `{prefix}#` header.
+fn table_lines(
+    prefix: &str,
+    benchmarks: Vec<Benchmark>,
+    total_millis: f64,
+    style: TableStyle,
+    total: &TotalOptions,
+    day_label: impl Fn(Day) -> String,
+) -> Vec<String> {
+    let total_bytes = sum_bytes(&benchmarks);
+    let mut lines = vec![];
+
+    if style.weeks {
+        let mut weeks: BTreeMap<u8, Vec<Benchmark>> = BTreeMap::new();
+        for bench in benchmarks {
+            let week = (bench.day.into_inner() - 1) / WEEK_DAYS + 1;
+            weeks.entry(week).or_default().push(bench);
+        }
+
+        for (week, benchmarks) in weeks {
+            let first = (week - 1) * WEEK_DAYS + 1;
+            let last = (first + WEEK_DAYS - 1).min(25);
+            let week_millis = benchmarks
+                .iter()
+                .map(|bench| bench.total_nanos)
+                .sum::<f64>()
+                / 1_000_000_f64;
+            let week_bytes = sum_bytes(&benchmarks);
+
+            lines.push(format!("{prefix}# Week {week} (days {first}-{last})"));
+            lines.push(String::new());
+            lines.extend(table_rows(benchmarks, style.samples, &day_label));
+            lines.push(String::new());
+            lines.push(format!(
+                "_Week {week} total: {} / {}_\n",
+                total.format(week_millis),
+                format_total_bytes(week_bytes)
+            ));
+        }
+    } else {
+        lines.extend(table_rows(benchmarks, style.samples, &day_label));
+        lines.push(String::new());
+    }
+
+    if !total.hidden {
+        lines.push(format!(
+            "**{}: {}**\n",
+            total.label("Total time"),
+            total.format(total_millis)
+        ));
+        lines.push(format!(
+            "**Total allocations: {}**\n",
+            format_total_bytes(total_bytes)
+        ));
+    }
 
+    lines
+}
+
+/// The header and the rows of a markdown table of benchmarks.
+fn table_rows(
+    benchmarks: Vec<Benchmark>,
+    samples: bool,
+    day_label: impl Fn(Day) -> String,
+) -> Vec<String> {
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
         "| Day | Part 1 | Part 2 |".into(),
         "| :---: | :---: | :---:  |".into(),
     ];
 
     for bench in benchmarks {
-        let path = get_path_for_bin(bench.day);
-        let (p1_time, p1_bytes) = bench.part_1.unwrap_or_else(|| ("-".into(), "-".into()));
-        let (p2_time, p2_bytes) = bench.part_2.unwrap_or_else(|| ("-".into(), "-".into()));
+        let label = day_label(bench.day);
+        if bench.timed_out {
+            lines.push(format!("| {label} | Timed out | Timed out |"));
+            continue;
+        }
 
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` / `{}` | `{}` / `{}` |",
-            bench.day.into_inner(),
-            path,
-            p1_time,
-            p1_bytes,
-            p2_time,
-            p2_bytes
-        ));
+        let format_part = |part: Option<(String, Bytes)>, count: Option<u128>| match part {
+            Some((time, bytes)) => match count.filter(|_| samples) {
+                Some(count) => format!("`{time}` (n={count}) / `{bytes}`"),
+                None => format!("`{time}` / `{bytes}`"),
+            },
+            None => "`-` / `-`".to_string(),
+        };
+        let [p1_samples, p2_samples] = bench.samples;
+        let part_1 = format_part(bench.part_1, p1_samples);
+        let part_2 = format_part(bench.part_2, p2_samples);
+
+        lines.push(format!("| {label} | {part_1} | {part_2} |"));
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total time: {total_millis:.2}ms**\n"));
-    lines.push(MARKER.into());
+    lines
+}
 
-    lines.join("\n")
+/// The heap allocations of both parts of the benchmarks.
+fn sum_bytes(benchmarks: &[Benchmark]) -> u64 {
+    benchmarks
+        .iter()
+        .flat_map(|bench| [&bench.part_1, &bench.part_2])
+        .flatten()
+        .map(|(_, bytes)| bytes.0)
+        .sum()
+}
+
+/// Formats a total of allocations in the most appropriate decimal unit, e.g. `1.50 MB`.
+fn format_total_bytes(bytes: u64) -> String {
+    format!(
+        "{:.2}",
+        byte_unit::Byte::from_u64(bytes).get_appropriate_unit(byte_unit::UnitType::Decimal)
+    )
+}
+
+/// Compares two benchmarks by total time, slowest first.
+///
+/// Timed out days are the slowest of all, and a `NaN` total is slower than any measured time.
+/// Days with the same total are ordered by ascending day, so that the order never depends on the
+/// order of the benchmarks.
+pub fn cmp_by_total(a: &Benchmark, b: &Benchmark) -> Ordering {
+    let total = |bench: &Benchmark| {
+        if bench.total_nanos.is_nan() {
+            f64::INFINITY
+        } else {
+            bench.total_nanos
+        }
+    };
+
+    b.timed_out
+        .cmp(&a.timed_out)
+        .then_with(|| total(b).total_cmp(&total(a)))
+        .then_with(|| a.day.cmp(&b.day))
+}
+
+/// Sorts benchmarks by total time, slowest first, see [`cmp_by_total`].
+pub fn sort_by_total(benchmarks: &mut [Benchmark]) {
+    benchmarks.sort_by(cmp_by_total);
 }
 
-fn update_content(s: &mut String, timings: Vec<Benchmark>, total_millis: f64) -> Result<(), Error> {
+/// Builds a markdown list of the benchmarked days ranked by total time, slowest first.
+///
+/// Each item reads `1. [Day N](path): `time``, the time being the total of both parts formatted
+/// as the total of the table, see [`TotalOptions::format`]. Without `links`, days are displayed as
+/// plain `Day N` text. Lines are separated by `\n`, the string does not end with a line break.
+pub fn construct_ranking(benchmarks: &[Benchmark], links: bool, total: &TotalOptions) -> String {
+    let mut ranked = benchmarks.to_vec();
+    sort_by_total(&mut ranked);
+
+    ranked
+        .into_iter()
+        .enumerate()
+        .map(|(index, bench)| {
+            let time = if bench.timed_out {
+                "Timed out".to_string()
+            } else {
+                format!("`{}`", total.format(bench.total_nanos / 1_000_000_f64))
+            };
+            format!("{}. {}: {time}", index + 1, day_label(bench.day, links))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Returns the content of the benchmarking table, markers included.
+fn table_content(readme: &str) -> Result<&str, Error> {
+    let positions = locate_table(readme)?;
+    Ok(&readme[positions.pos_start..positions.pos_end])
+}
+
+/// Returns `true` if most line breaks of the content are `\r\n`.
+fn uses_crlf(s: &str) -> bool {
+    let crlf = s.matches("\r\n").count();
+    let lf = s.matches('\n').count() - crlf;
+    crlf > lf
+}
+
+/// How to handle days that are benchmarked more than once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateDays {
+    /// Keeps the last benchmark of the day.
+    #[default]
+    KeepLast,
+    /// Fails the update with [`Error::DuplicateDay`].
+    Error,
+}
+
+/// How the total time of the days is displayed, in the console output of `all` as well as in the
+/// footer of the benchmarking table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TotalOptions {
+    /// Hides the total time, and the total allocations of the table.
+    pub hidden: bool,
+    /// Replaces the default label of the total time, e.g. `Grand total`.
+    pub label: Option<String>,
+    /// Always displays the total time in milliseconds, as done before the unit was picked from
+    /// the magnitude of the total.
+    pub millis: bool,
+}
+
+impl TotalOptions {
+    /// Returns the custom label if one is set, `default` otherwise.
+    pub fn label<'a>(&'a self, default: &'a str) -> &'a str {
+        self.label.as_deref().unwrap_or(default)
+    }
+
+    /// Formats the total time, in milliseconds or with [`format_duration_nanos`].
+    pub fn format(&self, total_millis: f64) -> String {
+        if self.millis {
+            format!("{total_millis:.2}ms")
+        } else {
+            format_duration_nanos(total_millis * 1_000_000_f64)
+        }
+    }
+}
+
+/// Rounds a value to a number of significant figures, e.g. `1234.5` to `1230` for 3 figures.
+fn round_significant(value: f64, figures: usize) -> f64 {
+    if value == 0_f64 || !value.is_finite() {
+        return value;
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let exponent = figures as i32 - 1 - value.abs().log10().floor() as i32;
+    let factor = 10_f64.powi(exponent);
+    (value * factor).round() / factor
+}
+
+/// Formats a duration in nanoseconds with a number of significant figures, in the unit picked by
+/// [`std::fmt::Debug`] for durations, e.g. `74.1ns`, `1.23ms` or `12.0s` for 3 figures.
+pub fn format_significant(nanos: f64, figures: usize) -> String {
+    let figures = figures.max(1);
+    // rounding first, so that e.g. `999.96µs` moves up to `1.00ms`.
+    let rounded = round_significant(nanos, figures);
+    let (factor, unit) = match rounded {
+        n if n < 1_000_f64 => (1_f64, "ns"),
+        n if n < 1_000_000_f64 => (1_000_f64, "\u{b5}s"),
+        n if n < 1_000_000_000_f64 => (1_000_000_f64, "ms"),
+        _ => (1_000_000_000_f64, "s"),
+    };
+
+    let value = round_significant(rounded / factor, figures);
+    #[allow(clippy::cast_possible_truncation)]
+    let integer_digits = if value == 0_f64 {
+        1
+    } else {
+        value.abs().log10().floor() as i64 + 1
+    };
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    let decimals = (figures as i64 - integer_digits).max(0) as usize;
+    format!("{value:.decimals$}{unit}")
+}
+
+/// Rounds the part timings of the benchmarks to a number of significant figures, so that the
+/// table looks uniform whatever precision the solutions printed. Timings that can not be parsed
+/// are kept as is.
+fn round_timings(benchmarks: &mut [Benchmark], figures: usize) {
+    for (timing, _) in benchmarks
+        .iter_mut()
+        .flat_map(|bench| [&mut bench.part_1, &mut bench.part_2])
+        .flatten()
+    {
+        if let Some(nanos) = parse_duration(timing) {
+            *timing = format_significant(nanos, figures);
+        }
+    }
+}
+
+/// Formats a duration in nanoseconds with two decimals in the most readable unit: microseconds
+/// below a millisecond, milliseconds below a second and seconds above, e.g. `12.35s`.
+pub fn format_duration_nanos(nanos: f64) -> String {
+    if nanos < 1_000_000_f64 {
+        format!("{:.2}\u{b5}s", nanos / 1_000_f64)
+    } else if nanos < 1_000_000_000_f64 {
+        format!("{:.2}ms", nanos / 1_000_000_f64)
+    } else {
+        format!("{:.2}s", nanos / 1_000_000_000_f64)
+    }
+}
+
+/// Options used when updating the benchmarking table.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateOptions {
+    /// Prints the lines that changed in the table before the file is written.
+    pub verbose: bool,
+    /// Appends a list of the days ranked by total time, slowest first.
+    pub sort_by_time: bool,
+    /// How to handle days that are benchmarked more than once.
+    pub duplicates: DuplicateDays,
+    /// Displays the days as plain `Day N` text instead of links to their solution, for READMEs
+    /// rendered where the relative paths do not resolve.
+    pub no_links: bool,
+    /// How the total time is displayed in the footer of the table.
+    pub total: TotalOptions,
+    /// Rounds the part timings to this number of significant figures. By default, the timings
+    /// are displayed as printed by the solutions.
+    pub precision: Option<usize>,
+    /// The title of the section header of the table, [`DEFAULT_HEADER`] by default.
+    pub header: Option<String>,
+    /// Annotates the timing of each part with the number of samples it was measured over.
+    pub show_samples: bool,
+    /// Groups the days of the table by week, with the subtotal of each week.
+    pub group_by_week: bool,
+}
+
+/// The default title of the section header of the benchmarking table.
+pub const DEFAULT_HEADER: &str = "Benchmarks";
+
+impl UpdateOptions {
+    /// The title of the section header of the table.
+    #[must_use]
+    pub fn header(&self) -> &str {
+        self.header.as_deref().unwrap_or(DEFAULT_HEADER)
+    }
+
+    /// How the benchmarks are rendered in the table.
+    #[must_use]
+    pub fn style(&self) -> TableStyle {
+        TableStyle {
+            links: !self.no_links,
+            samples: self.show_samples,
+            weeks: self.group_by_week,
+        }
+    }
+}
+
+/// Removes the benchmarks of days that appear more than once, keeping the last one in place of
+/// the first. Returns the removed benchmarks.
+fn dedup_days(
+    timings: &mut Vec<Benchmark>,
+    duplicates: DuplicateDays,
+) -> Result<Vec<Benchmark>, Error> {
+    let mut kept: Vec<Benchmark> = Vec::with_capacity(timings.len());
+    let mut removed = vec![];
+
+    for bench in timings.drain(..) {
+        match kept.iter_mut().find(|kept| kept.day == bench.day) {
+            Some(_) if duplicates == DuplicateDays::Error => {
+                return Err(Error::DuplicateDay(bench.day));
+            }
+            Some(previous) => removed.push(std::mem::replace(previous, bench)),
+            None => kept.push(bench),
+        }
+    }
+
+    *timings = kept;
+    Ok(removed)
+}
+
+/// Replaces the benchmarking table of `s` and returns the days that were benchmarked more than
+/// once, if any.
+fn update_content(
+    s: &mut String,
+    mut timings: Vec<Benchmark>,
+    mut total_millis: f64,
+    options: &UpdateOptions,
+) -> Result<Vec<Day>, Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+
+    let removed = dedup_days(&mut timings, options.duplicates)?;
+    // the total was computed with every run of the duplicated days.
+    total_millis -= removed.iter().map(|bench| bench.total_nanos).sum::<f64>() / 1_000_000_f64;
+
+    if let Some(figures) = options.precision {
+        round_timings(&mut timings, figures);
+    }
+
+    let style = options.style();
+    let ranking = options
+        .sort_by_time
+        .then(|| construct_ranking(&timings, style.links, &options.total));
+    let mut table = construct_table(
+        "##",
+        options.header(),
+        timings,
+        total_millis,
+        style,
+        &options.total,
+    );
+    if let Some(ranking) = ranking {
+        // the ranking is kept between the markers so that it is replaced on the next update.
+        table.truncate(table.len() - MARKER.len());
+        table.push_str(&format!("### Slowest first\n\n{ranking}\n\n{MARKER}"));
+    }
+    if uses_crlf(s) {
+        table = table.replace('\n', "\r\n");
+    }
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(removed.into_iter().map(|bench| bench.day).collect())
+}
+
+/// Replaces the benchmarking table of `s` with one table per year and returns the days that were
+/// benchmarked more than once, if any. The ranking of the slowest days is not supported across
+/// years and left out.
+fn update_years_content(
+    s: &mut String,
+    mut years: Vec<YearBenchmarks>,
+    options: &UpdateOptions,
+) -> Result<Vec<Day>, Error> {
+    let positions = locate_table(s)?;
+
+    let mut duplicated = vec![];
+    for year in &mut years {
+        let removed = dedup_days(&mut year.benchmarks, options.duplicates)?;
+        year.total_millis -=
+            removed.iter().map(|bench| bench.total_nanos).sum::<f64>() / 1_000_000_f64;
+        duplicated.extend(removed.into_iter().map(|bench| bench.day));
+
+        if let Some(figures) = options.precision {
+            round_timings(&mut year.benchmarks, figures);
+        }
+    }
+
+    let mut table = construct_year_tables(
+        "##",
+        options.header(),
+        years,
+        options.style(),
+        &options.total,
+    );
+    if uses_crlf(s) {
+        table = table.replace('\n', "\r\n");
+    }
     s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(duplicated)
+}
+
+fn parse_part(cell: &str) -> Option<(String, Bytes)> {
+    let (timing, bytes) = cell.split_once(" / ")?;
+    let timing = timing.split(" (n=").next()?.trim_matches('`');
+    let bytes = bytes.trim_matches('`');
+
+    if timing == "-" && bytes == "-" {
+        return None;
+    }
+
+    Some((timing.into(), bytes.parse().ok()?))
+}
+
+/// Parses the sample count annotation of a part cell, e.g. `100` for `` `10ms` (n=100) / `10 B` ``.
+fn parse_samples(cell: &str) -> Option<u128> {
+    cell.split_once(" (n=")?.1.split_once(')')?.0.parse().ok()
+}
+
+fn parse_row(line: &str) -> Option<Benchmark> {
+    let mut cells = line.trim().strip_prefix('|')?.split('|').map(str::trim);
+
+    // the day is either a link, `[Day N](path)`, or plain `Day N` text.
+    let label = cells.next()?;
+    let day = label
+        .strip_prefix('[')
+        .unwrap_or(label)
+        .strip_prefix("Day ")?
+        .split(']')
+        .next()?
+        .parse()
+        .ok()?;
+
+    let (part_1, part_2) = (cells.next()?, cells.next()?);
+    Some(Benchmark {
+        day,
+        part_1: parse_part(part_1),
+        part_2: parse_part(part_2),
+        total_nanos: 0_f64,
+        timed_out: line.contains("| Timed out |"),
+        samples: [parse_samples(part_1), parse_samples(part_2)],
+    })
+}
+
+/// Parses the rows of the benchmarking table back into [`Benchmark`] values.
+/// The `total_nanos` of the returned benchmarks are not recovered and set to zero.
+fn parse_table(readme: &str) -> Result<Vec<Benchmark>, Error> {
+    Ok(table_content(readme)?
+        .lines()
+        .filter_map(parse_row)
+        .collect())
+}
+
+/// Reads the benchmarks currently present in the README table.
+pub fn read() -> Result<Vec<Benchmark>, Error> {
+    let readme = String::from_utf8_lossy(&fs::read("README.md")?).to_string();
+    parse_table(&readme)
+}
+
+/// A line that differs between two versions of the benchmarking table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Added(String),
+    Removed(String),
+}
+
+/// Computes the lines removed from `old` and added in `new`, based on their longest common
+/// subsequence of lines. Unchanged lines are omitted.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push(DiffLine::Added(new[j].into()));
+            j += 1;
+        } else {
+            diff.push(DiffLine::Removed(old[i].into()));
+            i += 1;
+        }
+    }
+
+    diff
+}
+
+fn print_diff(diff: &[DiffLine]) {
+    if diff.is_empty() {
+        info!("README benchmarks are unchanged.");
+        return;
+    }
+
+    let theme = theme();
+    for line in diff {
+        match line {
+            DiffLine::Added(line) => println!("{}+ {line}{}", theme.success, theme.reset),
+            DiffLine::Removed(line) => println!("{}- {line}{}", theme.error, theme.reset),
+        }
+    }
+}
+
+/// The outcome of a successful [`update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateStatus {
+    /// The README was written with the new benchmarks.
+    Written,
+    /// The README already contained the same benchmarks, it was left untouched.
+    Unchanged,
+}
+
+/// Updates the benchmarking table of the README, as well as the progress badge if the README
+/// contains its markers. A warning is printed for each day that was benchmarked more than once.
+///
+/// The README is only written if its content changed, so that its modification time is kept.
+pub fn update(
+    timings: Vec<Benchmark>,
+    total_millis: f64,
+    options: &UpdateOptions,
+) -> Result<UpdateStatus, Error> {
+    let path = "README.md";
+    let readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_readme(&readme, timings, total_millis, options, |content| {
+        fs::write(path, content)
+    })
+}
+
+/// Returns what the README would become once updated by [`update`], without writing it. Warnings
+/// about the days benchmarked more than once are printed all the same.
+pub fn update_dry_run(
+    timings: Vec<Benchmark>,
+    total_millis: f64,
+    options: &UpdateOptions,
+) -> Result<String, Error> {
+    let readme = String::from_utf8_lossy(&fs::read("README.md")?).to_string();
+    updated_content(&readme, |updated| {
+        update_content(updated, timings, total_millis, options)
+    })
+}
+
+/// Returns what the README would become once updated by [`update_years`], without writing it.
+pub fn update_years_dry_run(
+    years: Vec<YearBenchmarks>,
+    options: &UpdateOptions,
+) -> Result<String, Error> {
+    let readme = String::from_utf8_lossy(&fs::read("README.md")?).to_string();
+    updated_content(&readme, |updated| {
+        update_years_content(updated, years, options)
+    })
+}
+
+/// Prints the lines of the README table that `updated`, e.g. the result of [`update_dry_run`],
+/// changes compared to the current README.
+pub fn print_changes(updated: &str) -> Result<(), Error> {
+    let readme = String::from_utf8_lossy(&fs::read("README.md")?).to_string();
+    print_diff(&diff_lines(
+        table_content(&readme)?,
+        table_content(updated)?,
+    ));
     Ok(())
 }
 
-pub fn update(timings: Vec<Benchmark>, total_millis: f64) -> Result<(), Error> {
+/// Updates the benchmarking table of the README with one table per year, as well as the progress
+/// badge if the README contains its markers. See [`update`].
+pub fn update_years(
+    years: Vec<YearBenchmarks>,
+    options: &UpdateOptions,
+) -> Result<UpdateStatus, Error> {
+    let path = "README.md";
+    let readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    apply_update(
+        &readme,
+        options,
+        |updated| update_years_content(updated, years, options),
+        |content| fs::write(path, content),
+    )
+}
+
+/// Updates the README content `readme` and passes the result to `write` if it changed.
+fn update_readme(
+    readme: &str,
+    timings: Vec<Benchmark>,
+    total_millis: f64,
+    options: &UpdateOptions,
+    write: impl FnOnce(&str) -> io::Result<()>,
+) -> Result<UpdateStatus, Error> {
+    apply_update(
+        readme,
+        options,
+        |updated| update_content(updated, timings, total_millis, options),
+        write,
+    )
+}
+
+/// Returns the README content `readme` with its table updated by `update_table`, which returns
+/// the duplicated days, and its progress badge updated.
+fn updated_content(
+    readme: &str,
+    update_table: impl FnOnce(&mut String) -> Result<Vec<Day>, Error>,
+) -> Result<String, Error> {
+    let mut updated = readme.to_string();
+    let duplicated = update_table(&mut updated)?;
+    update_badge(&mut updated, count_solved_days())?;
+
+    for day in duplicated {
+        warning!("Day {day} was benchmarked more than once, keeping its last benchmark.");
+    }
+
+    Ok(updated)
+}
+
+/// Updates the table of `readme` with `update_table`, which returns the duplicated days, and
+/// passes the result to `write` if it changed.
+fn apply_update(
+    readme: &str,
+    options: &UpdateOptions,
+    update_table: impl FnOnce(&mut String) -> Result<Vec<Day>, Error>,
+    write: impl FnOnce(&str) -> io::Result<()>,
+) -> Result<UpdateStatus, Error> {
+    let updated = updated_content(readme, update_table)?;
+
+    if options.verbose {
+        print_diff(&diff_lines(
+            table_content(readme)?,
+            table_content(&updated)?,
+        ));
+    }
+
+    if updated == readme {
+        return Ok(UpdateStatus::Unchanged);
+    }
+
+    write(&updated)?;
+    Ok(UpdateStatus::Written)
+}
+
+/// Removes the benchmarking table of `s`, keeping an empty pair of markers for the next update.
+fn clear_content(s: &mut String) -> Result<(), Error> {
+    let positions = locate_table(s)?;
+    s.replace_range(positions.pos_start..positions.pos_end, &MARKER.repeat(2));
+    Ok(())
+}
+
+/// Removes the benchmarking table from the README, leaving only its markers.
+pub fn clear() -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    clear_content(&mut readme)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -110,28 +969,40 @@ pub fn update(timings: Vec<Benchmark>, total_millis: f64) -> Result<(), Error> {
 #[cfg(test)]
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Benchmark, MARKER};
-    use crate::day;
+    use super::{
+        clear_content, construct_badge, construct_ranking, construct_table, construct_year_tables,
+        diff_lines, format_duration_nanos, format_significant, parse_table, sort_by_total,
+        update_badge, update_content, update_readme, updated_content, Benchmark, Bytes, DiffLine,
+        DuplicateDays, TableStyle, TotalOptions, UpdateOptions, UpdateStatus, YearBenchmarks,
+        BADGE_MARKER, MARKER,
+    };
+    use crate::{day, Day};
 
     fn get_mock_timings() -> Vec<Benchmark> {
         vec![
             Benchmark {
                 day: day!(1),
-                part_1: Some(("10ms".into(), "10 B".into())),
-                part_2: Some(("20ms".into(), "20 B".into())),
+                part_1: Some(("10ms".into(), Bytes(10))),
+                part_2: Some(("20ms".into(), Bytes(20))),
                 total_nanos: 3e+10,
+                timed_out: false,
+                samples: [Some(100_000), Some(1_000)],
             },
             Benchmark {
                 day: day!(2),
-                part_1: Some(("30ms".into(), "30 B".into())),
-                part_2: Some(("40ms".into(), "40 B".into())),
+                part_1: Some(("30ms".into(), Bytes(30))),
+                part_2: Some(("40ms".into(), Bytes(40))),
                 total_nanos: 7e+10,
+                timed_out: false,
+                samples: [Some(500), Some(500)],
             },
             Benchmark {
                 day: day!(4),
-                part_1: Some(("40ms".into(), "40 B".into())),
-                part_2: Some(("50ms".into(), "50 B".into())),
+                part_1: Some(("40ms".into(), Bytes(40))),
+                part_2: Some(("50ms".into(), Bytes(50))),
                 total_nanos: 9e+10,
+                timed_out: false,
+                samples: [Some(10), None],
             },
         ]
     }
@@ -140,36 +1011,155 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+    }
+
+    #[test]
+    fn displays_parser_errors() {
+        let mut s = "# readme".to_string();
+        let err = update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "could not parse the README: Could not find table start position."
+        );
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+    }
+
+    #[test]
+    fn updates_custom_header() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let options = UpdateOptions {
+            header: Some("Performance".into()),
+            ..Default::default()
+        };
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.matches("## Performance").collect::<Vec<&str>>().len(), 1);
+        assert!(!s.contains("## Benchmarks"));
+
+        // switching back replaces the custom header.
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert!(!s.contains("## Performance"));
+    }
+
+    #[test]
+    fn annotates_sample_counts() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` / `10 B` | `20ms` / `20 B` |"));
+        assert!(!s.contains("(n="));
+
+        let options = UpdateOptions {
+            show_samples: true,
+            ..Default::default()
+        };
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` (n=100000) / `10 B` | `20ms` (n=1000) / `20 B` |"
+        ));
+        // a part without a known sample count is not annotated.
+        assert!(
+            s.contains("| [Day 4](./src/bin/04.rs) | `40ms` (n=10) / `40 B` | `50ms` / `50 B` |")
+        );
+
+        let parsed = parse_table(&s).unwrap();
+        assert_eq!(parsed[0].part_1, Some(("10ms".into(), Bytes(10))));
+        assert_eq!(parsed[0].samples, [Some(100_000), Some(1_000)]);
+        assert_eq!(parsed[2].samples, [Some(10), None]);
+    }
+
+    #[test]
+    fn clears_benchmarks() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+        clear_content(&mut s).unwrap();
+        assert_eq!(s, format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER));
+        assert!(parse_table(&s).unwrap().is_empty());
+
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(parse_table(&s).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn dry_run_matches_written_content() {
+        let readme = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let options = UpdateOptions::default();
+
+        let preview = updated_content(&readme, |updated| {
+            update_content(updated, get_mock_timings(), 190.0, &options)
+        })
+        .unwrap();
+
+        let mut written = None;
+        update_readme(&readme, get_mock_timings(), 190.0, &options, |content| {
+            written = Some(content.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_ne!(preview, readme);
+        assert_eq!(written, Some(preview));
+    }
+
+    #[test]
+    fn skips_unchanged_writes() {
+        let mut readme = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let mut writes = 0;
+
+        for expected in [UpdateStatus::Written, UpdateStatus::Unchanged] {
+            let mut written = None;
+            let status = update_readme(
+                &readme,
+                get_mock_timings(),
+                190.0,
+                &UpdateOptions::default(),
+                |content| {
+                    written = Some(content.to_string());
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+            assert_eq!(status, expected);
+            if let Some(written) = written {
+                writes += 1;
+                readme = written;
+            }
+        }
+
+        assert_eq!(writes, 1);
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -184,10 +1174,456 @@ mod tests {
             "",
             "**Total time: 190.00ms**",
             "",
+            "**Total allocations: 190 B**",
+            "",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_formatted_benchmarks() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[1].part_2 = None;
+        update_content(&mut s, timings, 190.0, &UpdateOptions::default()).unwrap();
+
+        let parsed = parse_table(&s).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].day, day!(1));
+        assert_eq!(parsed[0].part_1, Some(("10ms".into(), Bytes(10))));
+        assert_eq!(parsed[1].day, day!(2));
+        assert_eq!(parsed[1].part_2, None);
+        assert_eq!(parsed[2].day, day!(4));
+        assert_eq!(parsed[2].part_2, Some(("50ms".into(), Bytes(50))));
+    }
+
+    #[test]
+    fn formats_days_without_links() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let options = UpdateOptions {
+            sort_by_time: true,
+            no_links: true,
+            ..Default::default()
+        };
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+
+        assert!(s.contains("| Day 1 | `10ms` / `10 B` | `20ms` / `20 B` |"));
+        assert!(s.contains("1. Day 4: `90.00s`"));
+        assert!(!s.contains("](./src/bin/"));
+
+        let parsed = parse_table(&s).unwrap();
+        assert_eq!(
+            parsed.iter().map(|bench| bench.day).collect::<Vec<_>>(),
+            [day!(1), day!(2), day!(4)]
+        );
+    }
+
+    #[test]
+    fn customizes_total() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut options = UpdateOptions {
+            total: TotalOptions {
+                label: Some("Grand total".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        assert!(s.contains("**Grand total: 190.00ms**"));
+
+        options.total.hidden = true;
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        assert!(!s.contains("total"));
+        assert!(!s.contains("Total"));
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) |"));
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration_nanos(0.0), "0.00\u{b5}s");
+        assert_eq!(format_duration_nanos(10_500.0), "10.50\u{b5}s");
+        assert_eq!(format_duration_nanos(999_990.0), "999.99\u{b5}s");
+        assert_eq!(format_duration_nanos(1_000_000.0), "1.00ms");
+        assert_eq!(format_duration_nanos(123_456_000.0), "123.46ms");
+        assert_eq!(format_duration_nanos(1e9), "1.00s");
+        assert_eq!(format_duration_nanos(12_345e9), "12345.00s");
+    }
+
+    #[test]
+    fn formats_significant_figures() {
+        assert_eq!(format_significant(74.13, 3), "74.1ns");
+        assert_eq!(format_significant(1_234.56, 3), "1.23\u{b5}s");
+        assert_eq!(format_significant(74_130_000.0, 3), "74.1ms");
+        assert_eq!(format_significant(1_234_560_000.0, 3), "1.23s");
+        assert_eq!(format_significant(999_960.0, 3), "1.00ms");
+        assert_eq!(format_significant(5.0, 3), "5.00ns");
+        assert_eq!(format_significant(0.0, 3), "0.00ns");
+        assert_eq!(format_significant(123_456.0, 1), "100\u{b5}s");
+    }
+
+    #[test]
+    fn rounds_table_timings() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[0].part_1 = Some(("74.13ns".into(), Bytes(10)));
+        timings[0].part_2 = Some(("1,234.56ms".into(), Bytes(20)));
+        timings[1].part_1 = Some(("Timed out".into(), Bytes(0)));
+        let options = UpdateOptions {
+            precision: Some(3),
+            ..Default::default()
+        };
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `74.1ns` / `10 B` | `1.23s` / `20 B` |"));
+        assert!(s.contains("| `Timed out` / `0 B` |"));
+    }
+
+    #[test]
+    fn formats_year_tables() {
+        let mut timings_2023 = get_mock_timings();
+        timings_2023.truncate(1);
+        let years = vec![
+            YearBenchmarks {
+                year: 2022,
+                benchmarks: vec![Benchmark {
+                    day: day!(4),
+                    part_1: Some(("5ms".into(), Bytes(1000))),
+                    part_2: None,
+                    total_nanos: 5_000_000_f64,
+                    timed_out: false,
+                    samples: [None; 2],
+                }],
+                total_millis: 5_f64,
+            },
+            YearBenchmarks {
+                year: 2023,
+                benchmarks: timings_2023,
+                total_millis: 30_f64,
+            },
+        ];
+        let table = construct_year_tables(
+            "##",
+            "Benchmarks",
+            years,
+            TableStyle::default(),
+            &TotalOptions::default(),
+        );
+
+        assert_eq!(
+            table,
+            [
+                MARKER,
+                "## Benchmarks",
+                "",
+                "### 2022",
+                "",
+                "| Day | Part 1 | Part 2 |",
+                "| :---: | :---: | :---:  |",
+                "| Day 4 | `5ms` / `1 KB` | `-` / `-` |",
+                "",
+                "**Total time: 5.00ms**\n",
+                "**Total allocations: 1.00 KB**\n",
+                "### 2023",
+                "",
+                "| Day | Part 1 | Part 2 |",
+                "| :---: | :---: | :---:  |",
+                "| Day 1 | `10ms` / `10 B` | `20ms` / `20 B` |",
+                "",
+                "**Total time: 30.00ms**\n",
+                "**Total allocations: 30 B**\n",
+                MARKER,
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn groups_days_by_week() {
+        let mut timings = get_mock_timings();
+        timings[2].day = day!(9);
+        // match the timings of the rows, so that the weekly subtotals add up to the total.
+        for (bench, total_nanos) in timings.iter_mut().zip([3e7, 7e7, 9e7]) {
+            bench.total_nanos = total_nanos;
+        }
+        let options = UpdateOptions {
+            no_links: true,
+            group_by_week: true,
+            ..Default::default()
+        };
+        let table = construct_table(
+            "##",
+            "Benchmarks",
+            timings,
+            190.0,
+            options.style(),
+            &options.total,
+        );
+
+        assert_eq!(
+            table,
+            [
+                MARKER,
+                "## Benchmarks",
+                "",
+                "### Week 1 (days 1-7)",
+                "",
+                "| Day | Part 1 | Part 2 |",
+                "| :---: | :---: | :---:  |",
+                "| Day 1 | `10ms` / `10 B` | `20ms` / `20 B` |",
+                "| Day 2 | `30ms` / `30 B` | `40ms` / `40 B` |",
+                "",
+                "_Week 1 total: 100.00ms / 100 B_\n",
+                "### Week 2 (days 8-14)",
+                "",
+                "| Day | Part 1 | Part 2 |",
+                "| :---: | :---: | :---:  |",
+                "| Day 9 | `40ms` / `40 B` | `50ms` / `50 B` |",
+                "",
+                "_Week 2 total: 90.00ms / 90 B_\n",
+                "**Total time: 190.00ms**\n",
+                "**Total allocations: 190 B**\n",
+                MARKER,
+            ]
+            .join("\n")
+        );
+
+        // the rows of every week are parsed back.
+        let mut s = format!("foo\n{MARKER}{MARKER}\nbar");
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        assert_eq!(parse_table(&s).unwrap().len(), 3);
+        assert!(s.contains("### Week 1 (days 1-7)"));
+        assert!(!s.contains("### Week 2"));
+    }
+
+    #[test]
+    fn links_year_solutions() {
+        let years = vec![YearBenchmarks {
+            year: 2022,
+            benchmarks: get_mock_timings(),
+            total_millis: 0_f64,
+        }];
+        let table = construct_year_tables(
+            "##",
+            "Benchmarks",
+            years,
+            TableStyle {
+                links: true,
+                ..Default::default()
+            },
+            &TotalOptions::default(),
+        );
+        assert!(table.contains("| [Day 1](./src/bin/01.rs) |"));
+    }
+
+    #[test]
+    fn forces_total_millis() {
+        let mut total = TotalOptions::default();
+        assert_eq!(total.format(0.01), "10.00\u{b5}s");
+        assert_eq!(total.format(12_345.0), "12.35s");
+
+        total.millis = true;
+        assert_eq!(total.format(0.01), "0.01ms");
+        assert_eq!(total.format(12_345.0), "12345.00ms");
+    }
+
+    #[test]
+    fn sums_allocations() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[0].part_1 = Some(("10ms".into(), Bytes(1_500_000)));
+        timings[1].part_2 = None;
+        update_content(&mut s, timings, 190.0, &UpdateOptions::default()).unwrap();
+        assert!(s.contains("**Total allocations: 1.50 MB**"));
+    }
+
+    #[test]
+    fn ranks_slowest_days_first() {
+        assert_eq!(
+            construct_ranking(&get_mock_timings(), true, &TotalOptions::default()),
+            [
+                "1. [Day 4](./src/bin/04.rs): `90.00s`",
+                "2. [Day 2](./src/bin/02.rs): `70.00s`",
+                "3. [Day 1](./src/bin/01.rs): `30.00s`",
+            ]
+            .join("\n")
+        );
+
+        let millis = TotalOptions {
+            millis: true,
+            ..Default::default()
+        };
+        assert!(construct_ranking(&get_mock_timings(), false, &millis)
+            .starts_with("1. Day 4: `90000.00ms`\n"));
+    }
+
+    #[test]
+    fn breaks_ranking_ties_by_day() {
+        let mut timings = get_mock_timings();
+        timings[0].total_nanos = 7e+10;
+        timings.swap(0, 1);
+        sort_by_total(&mut timings);
+        let days: Vec<Day> = timings.iter().map(|bench| bench.day).collect();
+        assert_eq!(days, [day!(4), day!(1), day!(2)]);
+
+        // a NaN total is ranked as the slowest measured time, after the timed out days.
+        timings[2].total_nanos = f64::NAN;
+        timings[1].timed_out = true;
+        sort_by_total(&mut timings);
+        let days: Vec<Day> = timings.iter().map(|bench| bench.day).collect();
+        assert_eq!(days, [day!(1), day!(2), day!(4)]);
+    }
+
+    #[test]
+    fn appends_ranking_inside_markers() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let ranking = UpdateOptions {
+            sort_by_time: true,
+            ..Default::default()
+        };
+        update_content(&mut s, get_mock_timings(), 190.0, &ranking).unwrap();
+        assert!(s.contains("### Slowest first\n\n1. [Day 4]"));
+        assert!(s.ends_with("`30.00s`\n\n<!--- benchmarking table --->\nbaz"));
+        assert_eq!(parse_table(&s).unwrap().len(), 3);
+
+        update_content(&mut s, get_mock_timings(), 190.0, &ranking).unwrap();
+        assert_eq!(s.matches("### Slowest first").count(), 1);
+    }
+
+    #[test]
+    fn formats_timed_out_days() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[0] = Benchmark {
+            day: day!(1),
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            timed_out: true,
+            samples: [None; 2],
+        };
+        update_content(&mut s, timings.clone(), 190.0, &UpdateOptions::default()).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | Timed out | Timed out |"));
+
+        let parsed = parse_table(&s).unwrap();
+        assert!(parsed[0].timed_out);
+        assert!(!parsed[1].timed_out);
+
+        assert!(construct_ranking(&timings, true, &TotalOptions::default())
+            .starts_with("1. [Day 1](./src/bin/01.rs): Timed out\n"));
+    }
+
+    #[test]
+    fn keeps_last_duplicate_day() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.push(Benchmark {
+            day: day!(1),
+            part_1: Some(("15ms".into(), Bytes(10))),
+            part_2: None,
+            total_nanos: 1e+10,
+            timed_out: false,
+            samples: [None; 2],
+        });
+
+        let total_millis = timings.iter().map(|bench| bench.total_nanos).sum::<f64>() / 1e6;
+        let duplicated =
+            update_content(&mut s, timings, total_millis, &UpdateOptions::default()).unwrap();
+        assert_eq!(duplicated, vec![day!(1)]);
+        assert_eq!(s.matches("[Day 1]").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `15ms` / `10 B` | `-` / `-` |"));
+        assert!(s.contains("**Total time: 170.00s**"));
+    }
+
+    #[test]
+    fn errors_on_duplicate_day() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.push(timings[0].clone());
+
+        let options = UpdateOptions {
+            duplicates: DuplicateDays::Error,
+            ..Default::default()
+        };
+        let err = update_content(&mut s, timings, 190.0, &options).unwrap_err();
+        assert_eq!(err.to_string(), "day 01 is benchmarked more than once");
+    }
+
+    #[test]
+    fn constructs_badge() {
+        assert_eq!(
+            construct_badge(0),
+            "![Progress](https://img.shields.io/badge/progress-0%2F25%20days-red)"
+        );
+        assert_eq!(
+            construct_badge(13),
+            "![Progress](https://img.shields.io/badge/progress-13%2F25%20days-yellow)"
+        );
+        assert_eq!(
+            construct_badge(25),
+            "![Progress](https://img.shields.io/badge/progress-25%2F25%20days-brightgreen)"
+        );
+    }
+
+    #[test]
+    fn updates_badge_between_markers() {
+        let mut s = format!("# AoC {}{}\nfoo", BADGE_MARKER, BADGE_MARKER);
+        update_badge(&mut s, 13).unwrap();
+        update_badge(&mut s, 14).unwrap();
+        assert_eq!(
+            s,
+            format!(
+                "# AoC {BADGE_MARKER}{}{BADGE_MARKER}\nfoo",
+                construct_badge(14)
+            )
+        );
+
+        let mut s = "# AoC".to_string();
+        update_badge(&mut s, 13).unwrap();
+        assert_eq!(s, "# AoC");
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let mut s = format!("foo\r\nbar\r\n{}\r\n{}\r\nbaz\r\n", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &UpdateOptions::default()).unwrap();
+        assert!(s.starts_with("foo\r\nbar\r\n"));
+        assert!(s.ends_with("\r\nbaz\r\n"));
+        assert!(s.contains("## Benchmarks\r\n"));
+        assert_eq!(s.matches('\n').count(), s.matches("\r\n").count());
+    }
+
+    #[test]
+    fn diffs_changed_lines() {
+        let old = "a\nb\nc\nd";
+        let new = "a\nx\nc\nd\ne";
+        assert_eq!(
+            diff_lines(old, new),
+            vec![
+                DiffLine::Added("x".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Added("e".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_identical_tables() {
+        let table = "a\nb\nc";
+        assert!(diff_lines(table, table).is_empty());
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        for formatted in ["10 B", "10 KB", "1.5 MB", "2.3 GB"] {
+            let bytes: Bytes = formatted.parse().unwrap();
+            assert_eq!(bytes.to_string(), formatted);
+        }
+
+        assert_eq!("10KB".parse::<Bytes>().unwrap(), Bytes(10_000));
+        assert_eq!("1 KiB".parse::<Bytes>().unwrap(), Bytes(1024));
+        assert!("ten bytes".parse::<Bytes>().is_err());
+    }
 }
@@ -4,22 +4,58 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use dhat;
-
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
-
-use super::ANSI_BOLD;
+use crate::template::output::SolutionOutput;
+use crate::template::theme::{theme, Theme};
+use crate::template::{alloc, aoc_cli};
+use crate::{Day, Part};
 
 struct RunResult<T> {
     result: T,
     duration: Duration,
     time_samples: u128,
-    bytes: u64,
+    /// The peak heap usage, only measured for timed runs.
+    bytes: Option<u64>,
+}
+
+/// The return types supported by the solution of a part:
+///  1. `Option<T>`, [`None`] for parts that are not solved yet.
+///  2. `Result<Option<T>, E>`, to surface errors such as a malformed input. The part is then
+///     reported as failed with the error as reason, instead of panicking.
+///
+/// The answer `T` and the error `E` can be of any type implementing [`Display`].
+pub trait PartResult {
+    /// The answer of the part, or the reason it failed.
+    fn answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
 }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+impl<T: Display, E: Display> PartResult for Result<Option<T>, E> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => answer.answer(),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// Runs and prints a solution part, returning its answer. The answer can be of any type
+/// implementing [`Display`], e.g. `u64`, `i64` or `String`, see [`PartResult`] for the supported
+/// return types. Nothing is run when another part is selected with the `--part` argument.
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+) -> Option<String> {
+    if selected_part().is_some_and(|selected| selected != part) {
+        return None;
+    }
+
     let part_str = format!("Part {part}");
 
     let RunResult {
@@ -28,37 +64,98 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
         time_samples,
         bytes,
     } = run(&func, input.clone(), |result| {
-        print_result(result, &part_str, "", "")
+        print_result(&result.answer(), &part_str, "", "");
     });
 
+    let answer = result.answer();
     print_result(
-        &result,
+        &answer,
         &part_str,
         &format_duration(&duration, time_samples),
         &format_bytes(bytes),
     );
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    answer.ok().flatten()
+}
+
+/// Runs a solution part once and returns its answer without printing anything, for the
+/// `--answers-only` mode, see [`print_answers`]. Nothing is run when another part is selected with
+/// the `--part` argument.
+pub fn answer_part<I, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+) -> Result<Option<String>, String> {
+    if selected_part().is_some_and(|selected| selected != part) {
+        return Ok(None);
+    }
+    func(input).answer()
+}
+
+/// Returns `true` if the `--answers-only` argument is passed. It cannot be combined with `--time`,
+/// since the timings are not printed.
+pub fn answers_only() -> bool {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|x| x == "--answers-only") {
+        return false;
+    }
+
+    if args.iter().any(|x| x == "--time") {
+        eprintln!("Unexpected command-line input. `--answers-only` cannot be used with `--time`.");
+        process::exit(1);
+    }
+    true
+}
+
+/// Formats the answers of both parts, one per line and without any decoration. A part without an
+/// answer is an empty line, so that the answer of part two is always on the second line.
+fn format_answers(answers: &[Result<Option<String>, String>; 2]) -> String {
+    answers
+        .iter()
+        .map(|answer| answer.as_ref().ok().cloned().flatten().unwrap_or_default())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Prints the answers of both parts for the `--answers-only` mode, see [`answer_part`].
+/// The errors of the failed parts are printed to stderr and the process exits with a non-zero
+/// status.
+pub fn print_answers(answers: [Result<Option<String>, String>; 2]) {
+    println!("{}", format_answers(&answers));
+
+    let mut failed = false;
+    for (part, answer) in Part::ALL.into_iter().zip(&answers) {
+        if let Err(e) = answer {
+            eprintln!("Part {part}: error: {e}");
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     When `--samples N` is passed, exactly `N` samples are benched instead.
+///     When `--min-time MS` is passed, sampling goes on until the samples ran for at least `MS` milliseconds.
+///
+/// The first execution is a warmup used to scale the sample count, it is never part of the samples.
+/// With `--time`, the heap usage is measured in another execution, so that counting the allocations
+/// does not slow down the samples. It is only measured if [`alloc::CountingAllocator`] is the
+/// global allocator of the solution.
 fn run<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> RunResult<T> {
     let timer = Instant::now();
-    let _ = func(input.clone());
-    let base_time = timer.elapsed();
-
-    let profiler = dhat::Profiler::new_heap();
     let result = func(input.clone());
-    let bytes = dhat::HeapStats::get().max_bytes.try_into().unwrap();
-    drop(profiler);
+    let base_time = timer.elapsed();
 
     hook(&result);
 
-    let time_bench = if std::env::args().any(|x| x == "--time") {
+    let timed = env::args().any(|x| x == "--time");
+    let bytes = (timed && alloc::is_installed()).then(|| alloc::measure(|| func(input.clone())).1);
+
+    let time_bench = if timed {
         bench(&func, input.clone(), &base_time)
     } else {
         (base_time, 1)
@@ -72,48 +169,87 @@ fn run<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> RunRe
     }
 }
 
-fn format_bytes(bytes: u64) -> String {
-    let byte = byte_unit::Byte::from_u64(bytes);
-    format!("({byte:#.1})")
+fn format_bytes(bytes: Option<u64>) -> String {
+    bytes.map_or_else(String::new, |bytes| {
+        let byte = byte_unit::Byte::from_u64(bytes);
+        format!(" ({byte:#.1})")
+    })
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    let theme = theme();
+    print!(" > {}benching{}", theme.emphasis, theme.reset);
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10_000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let fixed_samples = fixed_samples();
+    // an exact sample count takes precedence over the minimum duration.
+    let min_time = fixed_samples.is_none().then(min_time).flatten();
+    let bench_iterations = fixed_samples.unwrap_or_else(|| {
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10_000)
+    });
 
-    let mut timers: Vec<Duration> = vec![];
+    // only the sum of the timings is kept, the minimum duration may require millions of samples.
+    let mut samples: u128 = 0;
+    let mut total = Duration::ZERO;
 
-    for _ in 0..bench_iterations {
+    while samples < bench_iterations || min_time.is_some_and(|min| total < min) {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
         func(cloned);
-        timers.push(timer.elapsed());
+        total += timer.elapsed();
+        samples += 1;
     }
 
     (
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+        Duration::from_nanos((total.as_nanos() / samples) as u64),
+        samples,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Parses the `--samples` argument which forces the number of benchmark iterations.
+fn fixed_samples() -> Option<u128> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--samples")? + 1;
+
+    match args.get(index).and_then(|x| x.parse::<u128>().ok()) {
+        Some(samples) if samples > 0 => Some(samples),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --time --samples 1000");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parses the `--min-time` argument, the minimum duration in milliseconds the samples run for.
+fn min_time() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--min-time")? + 1;
+
+    match args.get(index).and_then(|x| x.parse::<u64>().ok()) {
+        Some(millis) => Some(Duration::from_millis(millis)),
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --time --min-time 500");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parses the `--part` argument which restricts the run to a single part.
+fn selected_part() -> Option<Part> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--part")? + 1;
+
+    match args.get(index).and_then(|x| x.parse::<Part>().ok()) {
+        Some(part) => Some(part),
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 2");
+            process::exit(1);
+        }
+    }
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -124,23 +260,50 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str, bytes_str: &str) {
+/// The line of a part that failed, e.g. `Part 1: ✖ error: invalid digit found in string`.
+fn format_error(part: &str, e: &str, theme: &Theme) -> String {
+    format!("{part}: ✖ {}error: {e}{}", theme.error, theme.reset)
+}
+
+fn print_result(
+    result: &Result<Option<String>, String>,
+    part: &str,
+    duration_str: &str,
+    bytes_str: &str,
+) {
     let is_intermediate_result = duration_str.is_empty();
 
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{}             ", format_error(part, e, theme()));
+            }
+            return;
+        }
+    };
+
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str} {bytes_str}");
+                let str = format!("{part}: ▼ {duration_str}{bytes_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
                     print!("\r");
                     println!("{str}");
-                    println!("{result}");
+                    // the answer block ends with a blank line, see `output::parse_answers`.
+                    println!("{}\n", result.trim_end_matches('\n'));
                 }
             } else {
-                let str =
-                    format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str} {bytes_str}");
+                let theme = theme();
+                let str = format!(
+                    "{part}: {}{result}{}{duration_str}{bytes_str}",
+                    theme.answer, theme.reset
+                );
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -163,32 +326,23 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str,
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+pub fn submit_output(
+    output: &SolutionOutput,
     day: Day,
-    part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
-
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let part_index = args.iter().position(|x| x == "--submit")? + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Some(part_submit) = args.get(part_index).and_then(|x| x.parse::<Part>().ok()) else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
 
-    if part_submit != part {
+    let Some((part, answer)) = output.iter().find(|(part, _)| *part == part_submit) else {
+        eprintln!("Part {part_submit} has no answer to submit.");
         return None;
-    }
+    };
 
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
@@ -196,5 +350,82 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part.into(), answer))
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::num::ParseIntError;
+
+    use super::{answer_part, format_answers, format_error, run_part};
+    use crate::part;
+    use crate::template::output::{parse_answer_line, parse_part_error};
+    use crate::template::theme::Theme;
+
+    #[test]
+    fn runs_string_solutions() {
+        let part_one = |input: &str| Some(input.to_uppercase());
+        assert_eq!(run_part(part_one, "abc", 1), Some("ABC".into()));
+
+        let part_two = |input: &str| Some(format!("{input}\n{input}"));
+        assert_eq!(run_part(part_two, "abc", 2), Some("abc\nabc".into()));
+    }
+
+    #[test]
+    fn runs_integer_solutions() {
+        let part_one = |input: &str| Some(u64::MAX - input.len() as u64);
+        assert_eq!(
+            run_part(part_one, "abc", 1),
+            Some("18446744073709551612".into())
+        );
+
+        let part_two = |input: &str| Some(-(input.len() as i64));
+        assert_eq!(run_part(part_two, "abc", 2), Some("-3".into()));
+    }
+
+    #[test]
+    fn runs_unsolved_parts() {
+        let part_one = |_: &str| None::<String>;
+        assert_eq!(run_part(part_one, "abc", 1), None);
+    }
+
+    #[test]
+    fn runs_result_solutions() {
+        let part_one = |input: &str| input.parse::<u32>().map(Some);
+        assert_eq!(run_part(part_one, "42", 1), Some("42".into()));
+        assert_eq!(run_part(part_one, "abc", 1), None);
+
+        let part_two = |_: &str| Ok::<Option<u32>, ParseIntError>(None);
+        assert_eq!(run_part(part_two, "abc", 2), None);
+    }
+
+    #[test]
+    fn formats_plain_answers() {
+        let part_one = |input: &str| Some(input.len());
+        let part_two = |input: &str| input.parse::<u32>().map(Some);
+
+        let answers = [
+            answer_part(part_one, "abc", 1),
+            answer_part(part_two, "abc", 2),
+        ];
+        assert!(answers[1].is_err());
+        assert_eq!(format_answers(&answers), "3\n");
+
+        let answers = [Ok(None), answer_part(part_two, "42", 2)];
+        assert_eq!(format_answers(&answers), "\n42");
+    }
+
+    #[test]
+    fn formats_failed_parts() {
+        let e = "abc".parse::<u32>().unwrap_err().to_string();
+        let line = format_error("Part 1", &e, &Theme::plain());
+
+        assert_eq!(line, "Part 1: ✖ error: invalid digit found in string");
+        assert_eq!(parse_answer_line(&line), None);
+        assert_eq!(
+            parse_part_error(&line),
+            Some((part!(1), "invalid digit found in string"))
+        );
+    }
 }
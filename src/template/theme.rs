@@ -0,0 +1,294 @@
+/// Styles used to format the output of the commands.
+///
+/// Each style can be overridden with an `AOC_COLOR_<STYLE>` environment variable holding ANSI SGR
+/// parameters (e.g. `AOC_COLOR_DAY="1;34"` for bold blue day headers), an empty value disables the
+/// style. Setting `NO_COLOR` disables every style.
+///
+/// The output is only styled when it is a terminal, unless the `--color` flag (or the `AOC_COLOR`
+/// environment variable) is set to `always` or `never`.
+use std::env;
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Day headers (`AOC_COLOR_DAY`).
+    pub day: String,
+    /// Labels of summary lines (`AOC_COLOR_LABEL`).
+    pub label: String,
+    /// Answers of the solutions (`AOC_COLOR_ANSWER`).
+    pub answer: String,
+    /// Secondary information such as totals and status messages (`AOC_COLOR_EMPHASIS`).
+    pub emphasis: String,
+    /// Successes and additions (`AOC_COLOR_SUCCESS`).
+    pub success: String,
+    /// Failures and removals (`AOC_COLOR_ERROR`).
+    pub error: String,
+    /// Resets the style, empty when every style is disabled.
+    pub reset: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            day: ANSI_BOLD.into(),
+            label: ANSI_BOLD.into(),
+            answer: ANSI_BOLD.into(),
+            emphasis: ANSI_ITALIC.into(),
+            success: ANSI_GREEN.into(),
+            error: ANSI_RED.into(),
+            reset: ANSI_RESET.into(),
+        }
+    }
+}
+
+impl Theme {
+    /// A theme without any style.
+    pub fn plain() -> Self {
+        Self {
+            day: String::new(),
+            label: String::new(),
+            answer: String::new(),
+            emphasis: String::new(),
+            success: String::new(),
+            error: String::new(),
+            reset: String::new(),
+        }
+    }
+
+    /// Builds the theme from the environment variables returned by `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        if var("NO_COLOR").is_some_and(|x| !x.is_empty()) {
+            return Self::plain();
+        }
+
+        let style = |name: &str, default: String| match var(&format!("AOC_COLOR_{name}")) {
+            Some(params) if params.is_empty() => String::new(),
+            Some(params) => format!("\x1b[{params}m"),
+            None => default,
+        };
+
+        let default = Self::default();
+        Self {
+            day: style("DAY", default.day),
+            label: style("LABEL", default.label),
+            answer: style("ANSWER", default.answer),
+            emphasis: style("EMPHASIS", default.emphasis),
+            success: style("SUCCESS", default.success),
+            error: style("ERROR", default.error),
+            reset: default.reset,
+        }
+    }
+}
+
+/// The environment variable holding the color choice, set by the `--color` flag so that the
+/// solutions, whose output is piped, follow the choice of the command.
+pub const COLOR_VAR: &str = "AOC_COLOR";
+
+/// When to color the output, selected with the `--color` flag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Colors the output, even when it is not a terminal and `NO_COLOR` is set.
+    Always,
+    /// Never colors the output.
+    Never,
+    /// Colors the output if it is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+}
+
+impl FromStr for ColorChoice {
+    type Err = ColorChoiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            "auto" => Ok(Self::Auto),
+            _ => Err(ColorChoiceError(s.to_string())),
+        }
+    }
+}
+
+impl Display for ColorChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Always => write!(f, "always"),
+            Self::Never => write!(f, "never"),
+            Self::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ColorChoice`].
+#[derive(Debug)]
+pub struct ColorChoiceError(String);
+
+impl std::error::Error for ColorChoiceError {}
+
+impl Display for ColorChoiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid color choice `{}`, expecting `always`, `never` or `auto`",
+            self.0
+        )
+    }
+}
+
+/// Resolves the theme of a color choice, the styles being read from the environment variables
+/// returned by `var`.
+pub fn resolve_theme(
+    choice: ColorChoice,
+    var: impl Fn(&str) -> Option<String>,
+    is_terminal: bool,
+) -> Theme {
+    match choice {
+        ColorChoice::Always => Theme::from_vars(|name| (name != "NO_COLOR").then(|| var(name))?),
+        ColorChoice::Never => Theme::plain(),
+        ColorChoice::Auto if is_terminal => Theme::from_vars(var),
+        ColorChoice::Auto => Theme::plain(),
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Returns the color choice stored in [`COLOR_VAR`], [`ColorChoice::Auto`] if it is not set.
+pub fn env_color_choice() -> ColorChoice {
+    env::var(COLOR_VAR)
+        .ok()
+        .and_then(|choice| choice.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Returns the theme of the color choice stored in [`COLOR_VAR`], read once.
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(|| {
+        resolve_theme(
+            env_color_choice(),
+            |name| env::var(name).ok(),
+            io::stdout().is_terminal(),
+        )
+    })
+}
+
+/// Sets the color choice of the command, before the theme is first used, and forwards the
+/// resolved choice to the solutions it runs.
+pub fn set_color_choice(choice: ColorChoice) {
+    let theme = resolve_theme(
+        choice,
+        |name| env::var(name).ok(),
+        io::stdout().is_terminal(),
+    );
+    let resolved = if theme == Theme::plain() {
+        ColorChoice::Never
+    } else {
+        ColorChoice::Always
+    };
+    env::set_var(COLOR_VAR, resolved.to_string());
+    let _ = THEME.set(theme);
+}
+
+/// Removes the ANSI escape sequences (e.g. colors) of a line.
+pub fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the sequence up to its final letter.
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{resolve_theme, strip_ansi, ColorChoice, Theme};
+
+    #[test]
+    fn default_theme() {
+        assert_eq!(Theme::from_vars(|_| None), Theme::default());
+    }
+
+    #[test]
+    fn no_color_disables_every_style() {
+        let theme = Theme::from_vars(|name| (name == "NO_COLOR").then(|| "1".into()));
+        assert_eq!(theme, Theme::plain());
+        for style in [
+            theme.day,
+            theme.label,
+            theme.answer,
+            theme.emphasis,
+            theme.success,
+            theme.error,
+            theme.reset,
+        ] {
+            assert!(style.is_empty());
+        }
+    }
+
+    #[test]
+    fn overrides_styles() {
+        let theme = Theme::from_vars(|name| match name {
+            "AOC_COLOR_DAY" => Some("1;34".into()),
+            "AOC_COLOR_EMPHASIS" => Some(String::new()),
+            _ => None,
+        });
+        assert_eq!(theme.day, "\x1b[1;34m");
+        assert_eq!(theme.emphasis, "");
+        assert_eq!(theme.label, Theme::default().label);
+    }
+
+    #[test]
+    fn strips_ansi_sequences() {
+        assert_eq!(strip_ansi("\x1b[1;32m42\x1b[0m (1ns)"), "42 (1ns)");
+        assert_eq!(strip_ansi("42"), "42");
+    }
+
+    #[test]
+    fn parses_color_choices() {
+        for choice in [ColorChoice::Always, ColorChoice::Never, ColorChoice::Auto] {
+            assert_eq!(choice.to_string().parse::<ColorChoice>().unwrap(), choice);
+        }
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn resolves_color_choices() {
+        let no_color = |name: &str| (name == "NO_COLOR").then(|| "1".into());
+
+        for is_terminal in [true, false] {
+            assert_eq!(
+                resolve_theme(ColorChoice::Always, no_color, is_terminal),
+                Theme::default()
+            );
+            assert_eq!(
+                resolve_theme(ColorChoice::Never, |_| None, is_terminal),
+                Theme::plain()
+            );
+        }
+
+        assert_eq!(
+            resolve_theme(ColorChoice::Auto, |_| None, true),
+            Theme::default()
+        );
+        assert_eq!(
+            resolve_theme(ColorChoice::Auto, |_| None, false),
+            Theme::plain()
+        );
+        assert_eq!(
+            resolve_theme(ColorChoice::Auto, no_color, true),
+            Theme::plain()
+        );
+    }
+}